@@ -1,5 +1,7 @@
 pub trait IdentExt {
   fn to_camel_from_snake(&self) -> Self;
+
+  fn to_pascal_from_snake(&self) -> Self;
 }
 
 impl IdentExt for syn::Ident {
@@ -27,6 +29,20 @@ impl IdentExt for syn::Ident {
 
     syn::Ident::new(&ident_name, span)
   }
+
+  fn to_pascal_from_snake(&self) -> Self {
+    let ident =
+      syn::ext::IdentExt::unraw(self).to_camel_from_snake().to_string();
+
+    let mut chars = ident.chars();
+
+    let ident_name = chars
+      .next()
+      .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+      .unwrap_or_default();
+
+    syn::Ident::new(&ident_name, self.span())
+  }
 }
 
 #[cfg(test)]
@@ -62,6 +78,105 @@ mod ident_to_camel_from_snake {
   }
 }
 
+#[cfg(test)]
+mod ident_to_pascal_from_snake {
+  use super::*;
+
+  #[test]
+  fn simple() {
+    let ident: syn::Ident = syn::parse_quote!(a_little_test);
+
+    assert_eq!(ident.to_pascal_from_snake().to_string(), "ALittleTest");
+  }
+
+  #[test]
+  fn raw() {
+    let ident: syn::Ident = syn::parse_quote!(r#type);
+
+    assert_eq!(ident.to_pascal_from_snake().to_string(), "Type");
+  }
+
+  #[test]
+  fn leading_underscore() {
+    let ident: syn::Ident = syn::parse_quote!(_little_test);
+
+    assert_eq!(ident.to_pascal_from_snake().to_string(), "LittleTest");
+  }
+}
+
+pub trait StrExt {
+  fn to_snake_from_camel(&self) -> String;
+}
+
+impl StrExt for str {
+  fn to_snake_from_camel(&self) -> String {
+    let mut snake_name = String::with_capacity(self.len());
+
+    let mut chars = self.chars().peekable();
+
+    let mut prev_is_lower = false;
+
+    while let Some(c) = chars.next() {
+      if c.is_uppercase() {
+        let next_is_lower = chars.peek().is_some_and(|c| c.is_lowercase());
+
+        if !snake_name.is_empty()
+          && !snake_name.ends_with('_')
+          && (prev_is_lower || next_is_lower)
+        {
+          snake_name.push('_');
+        }
+
+        snake_name.extend(c.to_lowercase());
+      } else if c.is_alphanumeric() {
+        snake_name.push(c);
+      } else if !snake_name.ends_with('_') {
+        snake_name.push('_');
+      }
+
+      prev_is_lower = c.is_lowercase() || c.is_numeric();
+    }
+
+    snake_name
+  }
+}
+
+#[cfg(test)]
+mod str_to_snake_from_camel {
+  use super::*;
+
+  #[test]
+  fn simple() {
+    assert_eq!("message".to_snake_from_camel(), "message");
+  }
+
+  #[test]
+  fn camel() {
+    assert_eq!(
+      "webkitAnimationEnd".to_snake_from_camel(),
+      "webkit_animation_end"
+    );
+  }
+
+  #[test]
+  fn acronyms() {
+    assert_eq!(
+      "DOMContentLoaded".to_snake_from_camel(),
+      "dom_content_loaded"
+    );
+  }
+
+  #[test]
+  fn with_numbers() {
+    assert_eq!("h264FrameDecoded".to_snake_from_camel(), "h264_frame_decoded");
+  }
+
+  #[test]
+  fn with_underscores() {
+    assert_eq!("my__customEvent".to_snake_from_camel(), "my_custom_event");
+  }
+}
+
 pub trait TokenStreamExt {
   fn to_pretty(&self) -> String;
 }
@@ -1,3 +1,66 @@
+//! Generates `wasm-bindgen` bindings from a struct, whose fields become
+//! getters and setters of JS properties, or from an `impl` block, whose
+//! methods become JS methods.
+//!
+//! # Dependencies
+//!
+//! The generated code refers to the crates below by absolute paths, so
+//! the crate using the macro must depend on them when using the
+//! features listed:
+//!
+//! - `wasm-bindgen`, always.
+//! - `js-sys`, for closure and `JsFn` arguments, `JsFn`, `Promise<T>`
+//!   and `AsyncIter<T>` values, `abortable` methods, events, event
+//!   handlers, `reflect`, `path`, `has` and `delete` fields, and
+//!   `iterable`, `array_like` and `record` structs.
+//! - `wasm-bindgen-futures`, for `async fn` methods, async closure
+//!   arguments, and `Promise<T>` and `AsyncIter<T>` values.
+//! - `futures`, for `AsyncIter<T>` values and the streams of events.
+//!
+//! # Closure arguments
+//!
+//! `impl Fn*` arguments are passed to JS as a `Closure`, which is kept
+//! alive depending on `#[opts(closure = ...)]`:
+//!
+//! - `leak`, the default for `Fn` and `FnMut`, leaks the `Closure`, so
+//!   JS can call it for as long as the program runs.
+//! - `once`, the default for `FnOnce`, lets JS free the `Closure` once
+//!   it is called.
+//! - `guard` returns the `Closure` to the caller, and JS can call it
+//!   until it is dropped. This changes the return type of the method:
+//!   `-> R` becomes `-> (R, Closure<dyn Fn*>)`, and a method returning
+//!   nothing returns the `Closure` alone. With several `guard`
+//!   arguments, their `Closure`s follow each other in the tuple, in
+//!   the order of the arguments.
+//!
+//! # Typed functions
+//!
+//! Fields and method returns of type `JsFn<(A, B), R>`, or
+//! `Option<JsFn<(A, B), R>>`, are bound as an `extern` type generated
+//! next to them, named after the type and the member, e.g.,
+//! `JsTypeOnMessageFn` for the `on_message` field of `JsType`. It is
+//! called with `call(a, b)`, returning `Result<R, JsValue>`, and can be
+//! created from a `js_sys::Function`, or from any
+//! `impl FnMut(A, B) -> R`, whose `Closure` is leaked. Setters take
+//! either, and so do `JsFn` arguments, which get a type of their own
+//! named after the method and the argument, e.g., `JsTypeFactoryFFn`
+//! for the `f` argument of `factory`. Functions typed for another
+//! member are passed through `js_sys::Function`.
+//!
+//! # Typed promises
+//!
+//! Fields and method returns of type `Promise<T>` are likewise bound as
+//! a generated `extern` type extending `js_sys::Promise`, e.g.,
+//! `JsTypeReadyPromise` for the `ready` field of `JsType`. It can be
+//! awaited for `Result<T, JsValue>`, failing if the promise rejects or
+//! resolves to another type, and stored or passed back to JS like any
+//! promise. Setters and `Promise<T>` arguments take any
+//! `impl AsRef<js_sys::Promise>`, so `T` isn't checked in argument
+//! position: a promise resolving to another type is passed as-is, and
+//! only fails once read back as `T`.
+
+#[macro_use]
+extern crate quote;
 #[macro_use]
 extern crate proc_macro_error;
 
@@ -304,24 +367,180 @@ mod macro_tests {
   }
 
   #[test]
-  fn simpl_impl() {
+  fn struct_can_use_nested_self_ty() {
     test_macro(
       quote! {
-        impl JsType {
-          fn example(&self);
+        #[opts(on = SomeType)]
+        struct JsType {
+          a: Option<Vec<Self>>,
+        }
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "a")]
+          fn a(this: &SomeType) -> Option<Vec<SomeType>>;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "a")]
+          fn set_a(this: &SomeType, value: Option<Vec<SomeType>>);
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_js_fn_field() {
+    test_macro(
+      quote! {
+        struct JsType {
+          /// Called on each message.
+          pub on_message: JsFn<(MessageEvent,), ()>,
+          format: Option<JsFn<(u32, String), String>>,
         }
       },
       quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type JsType;
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "onMessage")]
+          fn on_message_js(this: &JsType) -> JsTypeOnMessageFn;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "onMessage")]
+          fn set_on_message_js(this: &JsType, value: &JsTypeOnMessageFn);
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "format")]
+          fn format_js(this: &JsType) -> ::core::option::Option<JsTypeFormatFn>;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "format")]
+          fn set_format_js(
+            this: &JsType,
+            value: ::core::option::Option<&JsTypeFormatFn>,
+          );
+        }
+
         impl JsType {
-          fn example(&self) {
-            #[::wasm_bindgen::prelude::wasm_bindgen]
-            extern "C" {
-              #[wasm_bindgen(method)]
-              #[wasm_bindgen(js_name = "example")]
-              fn example_js(this: &JsType);
-            }
+          /// Called on each message.
+          pub fn on_message(&self) -> JsTypeOnMessageFn {
+            self.on_message_js()
+          }
 
-            self.example_js()
+          /// Called on each message.
+          pub fn set_on_message(
+            &self,
+            value: impl ::core::convert::Into<JsTypeOnMessageFn>,
+          ) {
+            self.set_on_message_js(&value.into())
+          }
+
+          fn format(&self) -> ::core::option::Option<JsTypeFormatFn> {
+            self.format_js()
+          }
+
+          fn set_format(&self, value: ::core::option::Option<JsTypeFormatFn>) {
+            self.set_format_js(value.as_ref())
+          }
+        }
+
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          #[doc = "A typed JS function, called with [`Self::call`]."]
+          #[wasm_bindgen(extends = ::js_sys::Function)]
+          #[wasm_bindgen(extends = ::js_sys::Object)]
+          #[wasm_bindgen(is_type_of = ::wasm_bindgen::JsValue::is_function)]
+          #[derive(Clone, Debug)]
+          pub type JsTypeOnMessageFn;
+        }
+
+        impl JsTypeOnMessageFn {
+          #[doc = "Calls the function, failing if it throws or returns another type."]
+          pub fn call(
+            &self,
+            arg0: MessageEvent,
+          ) -> ::core::result::Result<(), ::wasm_bindgen::JsValue> {
+            ::js_sys::Function::apply(
+              self,
+              &::wasm_bindgen::JsValue::UNDEFINED,
+              &[::wasm_bindgen::JsValue::from(arg0)]
+                .into_iter()
+                .collect::<::js_sys::Array>(),
+            )
+            .map(|_| ())
+          }
+        }
+
+        impl ::core::convert::From<::js_sys::Function> for JsTypeOnMessageFn {
+          fn from(function: ::js_sys::Function) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(function)
+          }
+        }
+
+        impl<F: FnMut(MessageEvent) + 'static> ::core::convert::From<F>
+          for JsTypeOnMessageFn
+        {
+          #[doc = "Leaks the closure, so JS can call it for as long as the program runs."]
+          fn from(f: F) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(
+              ::wasm_bindgen::closure::Closure::<dyn FnMut(MessageEvent)>::new(f)
+                .into_js_value(),
+            )
+          }
+        }
+
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          #[doc = "A typed JS function, called with [`Self::call`]."]
+          #[wasm_bindgen(extends = ::js_sys::Function)]
+          #[wasm_bindgen(extends = ::js_sys::Object)]
+          #[wasm_bindgen(is_type_of = ::wasm_bindgen::JsValue::is_function)]
+          #[derive(Clone, Debug)]
+          type JsTypeFormatFn;
+        }
+
+        impl JsTypeFormatFn {
+          #[doc = "Calls the function, failing if it throws or returns another type."]
+          fn call(
+            &self,
+            arg0: u32,
+            arg1: String,
+          ) -> ::core::result::Result<String, ::wasm_bindgen::JsValue> {
+            ::js_sys::Function::apply(
+              self,
+              &::wasm_bindgen::JsValue::UNDEFINED,
+              &[
+                ::wasm_bindgen::JsValue::from(arg0),
+                ::wasm_bindgen::JsValue::from(arg1),
+              ]
+              .into_iter()
+              .collect::<::js_sys::Array>(),
+            )
+            .and_then(
+              <String as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+            )
+          }
+        }
+
+        impl ::core::convert::From<::js_sys::Function> for JsTypeFormatFn {
+          fn from(function: ::js_sys::Function) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(function)
+          }
+        }
+
+        impl<F: FnMut(u32, String) -> String + 'static> ::core::convert::From<F>
+          for JsTypeFormatFn
+        {
+          #[doc = "Leaks the closure, so JS can call it for as long as the program runs."]
+          fn from(f: F) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(
+              ::wasm_bindgen::closure::Closure::<dyn FnMut(u32, String) -> String>::new(f)
+                .into_js_value(),
+            )
           }
         }
       },
@@ -329,24 +548,73 @@ mod macro_tests {
   }
 
   #[test]
-  fn impl_static() {
+  fn struct_with_promise_field() {
     test_macro(
       quote! {
-        impl JsType {
-          fn example();
+        struct JsType {
+          pub ready: Promise<String>,
         }
       },
       quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type JsType;
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "ready")]
+          fn ready_js(this: &JsType) -> JsTypeReadyPromise;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "ready")]
+          fn set_ready_js(this: &JsType, value: &::js_sys::Promise);
+        }
+
         impl JsType {
-          fn example() {
-            #[::wasm_bindgen::prelude::wasm_bindgen]
-            extern "C" {
-              #[wasm_bindgen(static_method_of = JsType)]
-              #[wasm_bindgen(js_name = "example")]
-              fn example_js();
-            }
+          pub fn ready(&self) -> JsTypeReadyPromise {
+            self.ready_js()
+          }
 
-            Self::example_js()
+          pub fn set_ready(
+            &self,
+            value: impl ::core::convert::AsRef<::js_sys::Promise>,
+          ) {
+            self.set_ready_js(value.as_ref())
+          }
+        }
+
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          #[doc = "A typed JS promise, which can be awaited for `Result<String, JsValue>`."]
+          #[wasm_bindgen(extends = ::js_sys::Promise)]
+          #[wasm_bindgen(extends = ::js_sys::Object)]
+          #[wasm_bindgen(
+            is_type_of = ::wasm_bindgen::JsValue::is_instance_of::<::js_sys::Promise>
+          )]
+          #[derive(Clone, Debug)]
+          pub type JsTypeReadyPromise;
+        }
+
+        impl ::core::future::IntoFuture for JsTypeReadyPromise {
+          type Output = ::core::result::Result<String, ::wasm_bindgen::JsValue>;
+
+          type IntoFuture = ::core::pin::Pin<
+            ::std::boxed::Box<dyn ::core::future::Future<Output = Self::Output>>,
+          >;
+
+          fn into_future(self) -> Self::IntoFuture {
+            ::std::boxed::Box::pin(async move {
+              ::wasm_bindgen_futures::JsFuture::from(::js_sys::Promise::from(self))
+                .await
+                .and_then(
+                  <String as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+                )
+            })
+          }
+        }
+
+        impl ::core::convert::From<::js_sys::Promise> for JsTypeReadyPromise {
+          fn from(promise: ::js_sys::Promise) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(promise)
           }
         }
       },
@@ -354,24 +622,64 @@ mod macro_tests {
   }
 
   #[test]
-  fn impl_can_map_value() {
+  fn struct_with_reflect_fields() {
     test_macro(
       quote! {
-        impl JsType {
-          fn example(&self) -> MapValue<T, U>;
+        struct JsType {
+          #[opts(reflect, js_name = "data-id")]
+          pub data_id: String,
+          #[opts(symbol = "Symbol.toStringTag", getter)]
+          pub tag: String,
         }
       },
       quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type JsType;
+        }
+
         impl JsType {
-          fn example(&self) -> U {
-            #[::wasm_bindgen::prelude::wasm_bindgen]
-            extern "C" {
-              #[wasm_bindgen(method)]
-              #[wasm_bindgen(js_name = "example")]
-              fn example_js(this: &JsType) -> T;
+          pub fn data_id(
+            &self,
+          ) -> ::core::result::Result<String, ::wasm_bindgen::JsValue> {
+            ::js_sys::Reflect::get(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              &::wasm_bindgen::JsValue::from("data-id"),
+            )
+            .and_then(
+              <String as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+            )
+          }
+
+          pub fn set_data_id(
+            &self,
+            value: String,
+          ) -> ::core::result::Result<(), ::wasm_bindgen::JsValue> {
+            let set = ::js_sys::Reflect::set(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              &::wasm_bindgen::JsValue::from("data-id"),
+              &::wasm_bindgen::JsValue::from(value),
+            )?;
+
+            if set {
+              ::core::result::Result::Ok(())
+            } else {
+              ::core::result::Result::Err(
+                ::js_sys::TypeError::new("failed to set `data-id`").into(),
+              )
             }
+          }
 
-            self.example_js()
+          pub fn tag(
+            &self,
+          ) -> ::core::result::Result<String, ::wasm_bindgen::JsValue> {
+            ::js_sys::Reflect::get(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              &::wasm_bindgen::JsValue::from(::js_sys::Symbol::to_string_tag()),
+            )
+            .and_then(
+              <String as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+            )
           }
         }
       },
@@ -379,24 +687,79 @@ mod macro_tests {
   }
 
   #[test]
-  fn impl_can_async_with_args_can_map_value() {
+  fn struct_with_path_field() {
     test_macro(
       quote! {
-        impl JsType {
-          async fn example(&self, a: String) -> MapValue<T, U>;
+        struct Config {
+          #[opts(path = "server?.port")]
+          pub port: f64,
         }
       },
       quote! {
-        impl JsType {
-          async fn example(&self, a: String) -> U {
-            #[::wasm_bindgen::prelude::wasm_bindgen]
-            extern "C" {
-              #[wasm_bindgen(method)]
-              #[wasm_bindgen(js_name = "example")]
-              async fn example_js(this: &JsType, a: String) -> T;
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type Config;
+        }
+
+        impl Config {
+          pub fn port(
+            &self,
+          ) -> ::core::result::Result<
+            ::core::option::Option<f64>,
+            ::wasm_bindgen::JsValue,
+          > {
+            let target = ::core::clone::Clone::clone(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+            );
+
+            let target = ::js_sys::Reflect::get(
+              &target,
+              &::wasm_bindgen::JsValue::from("server"),
+            )?;
+
+            if target.is_null() || target.is_undefined() {
+              return ::core::result::Result::Ok(::core::option::Option::None);
             }
 
-            self.example_js(a).await
+            ::js_sys::Reflect::get(&target, &::wasm_bindgen::JsValue::from("port"))
+              .and_then(
+                <f64 as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+              )
+              .map(::core::option::Option::Some)
+          }
+
+          pub fn set_port(
+            &self,
+            value: f64,
+          ) -> ::core::result::Result<(), ::wasm_bindgen::JsValue> {
+            let target = ::core::clone::Clone::clone(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+            );
+
+            let target = ::js_sys::Reflect::get(
+              &target,
+              &::wasm_bindgen::JsValue::from("server"),
+            )?;
+
+            if target.is_null() || target.is_undefined() {
+              return ::core::result::Result::Err(
+                ::js_sys::TypeError::new("`server` is null or undefined").into(),
+              );
+            }
+
+            let set = ::js_sys::Reflect::set(
+              &target,
+              &::wasm_bindgen::JsValue::from("port"),
+              &::wasm_bindgen::JsValue::from(value),
+            )?;
+
+            if set {
+              ::core::result::Result::Ok(())
+            } else {
+              ::core::result::Result::Err(
+                ::js_sys::TypeError::new("failed to set `server.port`").into(),
+              )
+            }
           }
         }
       },
@@ -404,26 +767,2094 @@ mod macro_tests {
   }
 
   #[test]
-  fn impl_can_async_with_args_can_map_value_with_block() {
+  fn struct_with_has_and_delete() {
     test_macro(
       quote! {
+        struct JsType {
+          #[opts(has, delete)]
+          pub my_prop: String,
+        }
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type JsType;
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "myProp")]
+          pub fn my_prop(this: &JsType) -> String;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "myProp")]
+          pub fn set_my_prop(this: &JsType, value: String);
+        }
+
         impl JsType {
-          async fn example(&self, a: String) -> MapValue<T, U> {
-            self.example_js(a).await.into()
+          #[doc = "Whether the `myProp` property is present, using the JS `in` operator."]
+          pub fn has_my_prop(&self) -> bool {
+            ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::has(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              &::wasm_bindgen::JsValue::from("myProp"),
+            ))
+          }
+
+          #[doc = "Deletes the `myProp` property, returning whether it succeeded."]
+          pub fn delete_my_prop(&self) -> bool {
+            ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+              ::js_sys::Reflect::delete_property(
+                ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Object>(self),
+                &::wasm_bindgen::JsValue::from("myProp"),
+              ),
+            )
           }
         }
       },
+    );
+  }
+
+  #[test]
+  fn struct_with_nullable_fields() {
+    test_macro(
+      quote! {
+        struct JsType {
+          pub title: Nullable<String>,
+          pub timeout: Maybe<f64>,
+        }
+      },
       quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type JsType;
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "title")]
+          fn title_js(this: &JsType) -> ::wasm_bindgen::JsValue;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "title")]
+          fn set_title_js(this: &JsType, value: &::wasm_bindgen::JsValue);
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "timeout")]
+          fn timeout_js(this: &JsType) -> ::wasm_bindgen::JsValue;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "timeout")]
+          fn set_timeout_js(this: &JsType, value: &::wasm_bindgen::JsValue);
+        }
+
         impl JsType {
-          async fn example(&self, a: String) -> U {
-            #[::wasm_bindgen::prelude::wasm_bindgen]
-            extern "C" {
-              #[wasm_bindgen(method)]
-              #[wasm_bindgen(js_name = "example")]
-              async fn example_js(this: &JsType, a: String) -> T;
+          pub fn title(&self) -> ::core::option::Option<String> {
+            let value: ::wasm_bindgen::JsValue = self.title_js();
+
+            if value.is_null() || value.is_undefined() {
+              ::core::option::Option::None
+            } else {
+              ::core::option::Option::Some(::wasm_bindgen::UnwrapThrowExt::expect_throw(
+                <String as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value(
+                  value,
+                ),
+                "`title` is not of the expected type",
+              ))
             }
+          }
 
-            self.example_js(a).await.into()
+          pub fn set_title(&self, value: ::core::option::Option<String>) {
+            self.set_title_js(
+              &value
+                .map_or(::wasm_bindgen::JsValue::NULL, ::wasm_bindgen::JsValue::from),
+            )
+          }
+
+          pub fn timeout(&self) -> ::core::option::Option<::core::option::Option<f64>> {
+            let value: ::wasm_bindgen::JsValue = self.timeout_js();
+
+            if value.is_undefined() {
+              ::core::option::Option::None
+            } else if value.is_null() {
+              ::core::option::Option::Some(::core::option::Option::None)
+            } else {
+              ::core::option::Option::Some(::core::option::Option::Some(
+                ::wasm_bindgen::UnwrapThrowExt::expect_throw(
+                  <f64 as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value(
+                    value,
+                  ),
+                  "`timeout` is not of the expected type",
+                ),
+              ))
+            }
+          }
+
+          pub fn set_timeout(
+            &self,
+            value: ::core::option::Option<::core::option::Option<f64>>,
+          ) {
+            self.set_timeout_js(&match value {
+              ::core::option::Option::None => ::wasm_bindgen::JsValue::UNDEFINED,
+              ::core::option::Option::Some(::core::option::Option::None) => {
+                ::wasm_bindgen::JsValue::NULL
+              }
+              ::core::option::Option::Some(::core::option::Option::Some(value)) => {
+                ::wasm_bindgen::JsValue::from(value)
+              }
+            })
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_default_fields() {
+    test_macro(
+      quote! {
+        struct JsType {
+          #[opts(default = 5000.0)]
+          pub timeout: f64,
+          #[opts(default)]
+          pub name: String,
+        }
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type JsType;
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "timeout")]
+          fn timeout_js(this: &JsType) -> ::core::option::Option<f64>;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "timeout")]
+          fn set_timeout_js(this: &JsType, value: f64);
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "name")]
+          fn name_js(this: &JsType) -> ::core::option::Option<String>;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "name")]
+          fn set_name_js(this: &JsType, value: String);
+        }
+
+        impl JsType {
+          pub fn timeout(&self) -> f64 {
+            self.timeout_js().unwrap_or_else(|| 5000.0)
+          }
+
+          pub fn set_timeout(&self, value: f64) {
+            self.set_timeout_js(value)
+          }
+
+          pub fn name(&self) -> String {
+            self
+              .name_js()
+              .unwrap_or_else(|| ::core::default::Default::default())
+          }
+
+          pub fn set_name(&self, value: String) {
+            self.set_name_js(value)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_asymmetric_field_types() {
+    test_macro(
+      quote! {
+        struct JsType {
+          #[opts(set_ty = &str)]
+          pub name: String,
+          #[opts(get_ty = String, set_ty = &JsValue)]
+          pub value: JsValue,
+        }
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type JsType;
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "name")]
+          pub fn name(this: &JsType) -> String;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "name")]
+          pub fn set_name(this: &JsType, value: &str);
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "value")]
+          pub fn value(this: &JsType) -> String;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "value")]
+          pub fn set_value(this: &JsType, value: &JsValue);
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_custom_method_names() {
+    test_macro(
+      quote! {
+        #[opts(getter_prefix = "get_", setter_prefix = "with_")]
+        struct JsType {
+          pub name: String,
+          #[opts(getter_name = is_visible)]
+          pub visible: bool,
+          #[opts(default = 0, setter_name = resize)]
+          pub size: u32,
+        }
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type JsType;
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "name")]
+          pub fn get_name(this: &JsType) -> String;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "name")]
+          pub fn with_name(this: &JsType, value: String);
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "visible")]
+          pub fn is_visible(this: &JsType) -> bool;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "visible")]
+          pub fn with_visible(this: &JsType, value: bool);
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "size")]
+          fn size_js(this: &JsType) -> ::core::option::Option<u32>;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "size")]
+          fn set_size_js(this: &JsType, value: u32);
+        }
+
+        impl JsType {
+          pub fn get_size(&self) -> u32 {
+            self.size_js().unwrap_or_else(|| 0)
+          }
+
+          pub fn resize(&self, value: u32) {
+            self.set_size_js(value)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_event_handler_field() {
+    test_macro(
+      quote! {
+        pub struct JsType {
+          #[opts(event_handler)]
+          pub onclick: MouseEvent,
+        }
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          pub type JsType;
+
+          #[wasm_bindgen(method, getter)]
+          #[wasm_bindgen(js_name = "onclick")]
+          fn onclick_js(this: &JsType) -> ::core::option::Option<::js_sys::Function>;
+
+          #[wasm_bindgen(method, setter)]
+          #[wasm_bindgen(js_name = "onclick")]
+          fn set_onclick_js(
+            this: &JsType,
+            value: ::core::option::Option<&::js_sys::Function>,
+          );
+        }
+
+        impl JsType {
+          pub fn onclick(&self) -> ::core::option::Option<::js_sys::Function> {
+            self.onclick_js()
+          }
+
+          pub fn set_onclick(
+            &self,
+            handler: impl FnMut(MouseEvent) + 'static,
+          ) -> JsTypeEventHandler {
+            let closure = ::wasm_bindgen::closure::Closure::<
+              dyn FnMut(MouseEvent),
+            >::new(handler);
+
+            let function = ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Function>(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(&closure),
+            )
+            .clone();
+
+            self.set_onclick_js(::core::option::Option::Some(&function));
+
+            JsTypeEventHandler {
+              this: ::wasm_bindgen::JsCast::unchecked_into(
+                ::core::clone::Clone::clone(
+                  ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+                ),
+              ),
+              function,
+              clear: |this: &JsType, function: &::js_sys::Function| {
+                if this.onclick_js().as_ref() == ::core::option::Option::Some(function) {
+                  this.set_onclick_js(::core::option::Option::None);
+                }
+              },
+              _closure: ::std::boxed::Box::new(closure),
+            }
+          }
+        }
+
+        #[doc = "Keeps an event handler set on [`JsType`] alive, clearing it once dropped."]
+        #[must_use = "the event handler is cleared once the guard is dropped"]
+        pub struct JsTypeEventHandler {
+          this: JsType,
+          function: ::js_sys::Function,
+          clear: fn(&JsType, &::js_sys::Function),
+          _closure: ::std::boxed::Box<dyn ::core::any::Any>,
+        }
+
+        impl ::core::ops::Drop for JsTypeEventHandler {
+          fn drop(&mut self) {
+            (self.clear)(&self.this, &self.function);
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_iterable() {
+    test_macro(
+      quote! {
+        #[opts(iterable = Node)]
+        pub struct NodeList {}
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          pub type NodeList;
+        }
+
+        #[doc = "Iterator over the items of [`NodeList`]."]
+        pub struct NodeListIter(::js_sys::IntoIter);
+
+        impl ::core::iter::Iterator for NodeListIter {
+          type Item = Node;
+
+          fn next(&mut self) -> ::core::option::Option<Self::Item> {
+            self.0.next().map(|item|
+              ::wasm_bindgen::JsCast::unchecked_into::<Node>(
+                ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(item),
+              ))
+          }
+        }
+
+        impl NodeList {
+          /// Iterates over the items through `Symbol.iterator`.
+          pub fn iter(&self) -> NodeListIter {
+            let iter = ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+              ::js_sys::try_iter(
+                ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              ),
+            );
+
+            NodeListIter(
+              ::wasm_bindgen::UnwrapThrowExt::expect_throw(
+                iter,
+                "`NodeList` is not iterable",
+              ),
+            )
+          }
+        }
+
+        impl<'a> ::core::iter::IntoIterator for &'a NodeList {
+          type Item = Node;
+
+          type IntoIter = NodeListIter;
+
+          fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_converted_iterable() {
+    test_macro(
+      quote! {
+        #[opts(iterable = String)]
+        pub struct Names {}
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          pub type Names;
+        }
+
+        #[doc = "Iterator over the items of [`Names`]."]
+        pub struct NamesIter(::js_sys::IntoIter);
+
+        impl ::core::iter::Iterator for NamesIter {
+          type Item = String;
+
+          fn next(&mut self) -> ::core::option::Option<Self::Item> {
+            self.0.next().map(|item|
+              ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+                <String as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value(
+                  ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(item),
+                ),
+              ))
+          }
+        }
+
+        impl Names {
+          /// Iterates over the items through `Symbol.iterator`.
+          pub fn iter(&self) -> NamesIter {
+            let iter = ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+              ::js_sys::try_iter(
+                ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              ),
+            );
+
+            NamesIter(
+              ::wasm_bindgen::UnwrapThrowExt::expect_throw(
+                iter,
+                "`Names` is not iterable",
+              ),
+            )
+          }
+        }
+
+        impl<'a> ::core::iter::IntoIterator for &'a Names {
+          type Item = String;
+
+          type IntoIter = NamesIter;
+
+          fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_array_like() {
+    test_macro(
+      quote! {
+        #[opts(array_like = File)]
+        pub struct FileList {}
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          pub type FileList;
+        }
+
+        #[doc = "Iterator over the items of [`FileList`]."]
+        pub struct FileListIter {
+          this: FileList,
+          index: u32,
+        }
+
+        impl ::core::iter::Iterator for FileListIter {
+          type Item = File;
+
+          fn next(&mut self) -> ::core::option::Option<Self::Item> {
+            let item = self.this.get(self.index)?;
+
+            self.index += 1;
+
+            ::core::option::Option::Some(item)
+          }
+        }
+
+        impl FileList {
+          /// The number of items, read from `length`.
+          pub fn len(&self) -> u32 {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method, getter)]
+              #[wasm_bindgen(js_name = "length")]
+              fn length_js(this: &FileList) -> u32;
+            }
+
+            self.length_js()
+          }
+
+          /// Whether there are no items.
+          pub fn is_empty(&self) -> bool {
+            self.len() == 0
+          }
+
+          /// The item at `index`, or `None` if it is out of bounds.
+          pub fn get(&self, index: u32) -> ::core::option::Option<File> {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method, indexing_getter)]
+              fn get_js(this: &FileList, index: u32) -> File;
+            }
+
+            (index < self.len()).then(|| self.get_js(index))
+          }
+
+          /// Sets the item at `index`.
+          pub fn set(&self, index: u32, value: File) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method, indexing_setter)]
+              fn set_js(this: &FileList, index: u32, value: File);
+            }
+
+            self.set_js(index, value)
+          }
+
+          /// Iterates over the items, up to the `length` at each step.
+          pub fn iter(&self) -> FileListIter {
+            FileListIter {
+              this: ::wasm_bindgen::JsCast::unchecked_into(
+                ::core::clone::Clone::clone(
+                  ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+                ),
+              ),
+              index: 0,
+            }
+          }
+
+          /// Collects the items into a `Vec`.
+          pub fn to_vec(&self) -> ::std::vec::Vec<File> {
+            self.iter().collect()
+          }
+        }
+
+        impl<'a> ::core::iter::IntoIterator for &'a FileList {
+          type Item = File;
+
+          type IntoIter = FileListIter;
+
+          fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_record() {
+    test_macro(
+      quote! {
+        #[opts(record = JsString)]
+        pub struct Dataset {}
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          pub type Dataset;
+        }
+
+        impl Dataset {
+          /// The value of the own enumerable property `key`, or `None` if
+          /// there is none.
+          pub fn get(&self, key: &str) -> ::core::option::Option<JsString> {
+            self.has(key).then(|| {
+              let value =
+                ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::get(
+                  ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+                  &::wasm_bindgen::JsValue::from_str(key),
+                ));
+              ::wasm_bindgen::JsCast::unchecked_into::<JsString>(value)
+            })
+          }
+          /// Sets the property `key` to `value`.
+          pub fn set(&self, key: &str, value: JsString) {
+            ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::set(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              &::wasm_bindgen::JsValue::from_str(key),
+              &::wasm_bindgen::JsValue::from(value),
+            ));
+          }
+          /// Whether there is an own enumerable property `key`, so the
+          /// same keys as listed by `keys`.
+          pub fn has(&self, key: &str) -> bool {
+            ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Object>(self)
+              .property_is_enumerable(&::wasm_bindgen::JsValue::from_str(key))
+          }
+          /// Deletes the property `key`, returning whether it succeeded.
+          pub fn delete(&self, key: &str) -> bool {
+            ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+              ::js_sys::Reflect::delete_property(
+                ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Object>(self),
+                &::wasm_bindgen::JsValue::from_str(key),
+              ),
+            )
+          }
+          /// The keys of the own enumerable properties, from
+          /// `Object.keys`.
+          pub fn keys(&self) -> ::std::vec::Vec<::std::string::String> {
+            ::js_sys::Object::keys(::wasm_bindgen::JsCast::unchecked_ref::<
+              ::js_sys::Object,
+            >(self))
+            .iter()
+            .map(|key| ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(key.as_string()))
+            .collect()
+          }
+          /// The keys and values of the own enumerable properties, from
+          /// `Object.entries`.
+          pub fn entries(&self) -> ::std::vec::Vec<(::std::string::String, JsString)> {
+            ::js_sys::Object::entries(::wasm_bindgen::JsCast::unchecked_ref::<
+              ::js_sys::Object,
+            >(self))
+            .iter()
+            .map(|entry| {
+              let entry =
+                ::wasm_bindgen::JsCast::unchecked_into::<::js_sys::Array>(entry);
+              let key =
+                ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(entry.get(0).as_string());
+              let value = entry.get(1);
+              (
+                key,
+                ::wasm_bindgen::JsCast::unchecked_into::<JsString>(value),
+              )
+            })
+            .collect()
+          }
+          /// The number of own enumerable properties.
+          pub fn len(&self) -> u32 {
+            ::js_sys::Object::keys(::wasm_bindgen::JsCast::unchecked_ref::<
+              ::js_sys::Object,
+            >(self))
+            .length()
+          }
+          /// Whether there are no own enumerable properties.
+          pub fn is_empty(&self) -> bool {
+            self.len() == 0
+          }
+        }
+
+        impl
+          ::core::convert::From<
+            ::std::collections::HashMap<::std::string::String, JsString>,
+          > for Dataset
+        {
+          fn from(
+            map: ::std::collections::HashMap<::std::string::String, JsString>,
+          ) -> Self {
+            let object = ::js_sys::Object::new();
+            for (key, value) in map {
+              ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::set(
+                &object,
+                &::wasm_bindgen::JsValue::from(key),
+                &::wasm_bindgen::JsValue::from(value),
+              ));
+            }
+            ::wasm_bindgen::JsCast::unchecked_into(object)
+          }
+        }
+
+        impl ::core::convert::From<&Dataset>
+          for ::std::collections::HashMap<::std::string::String, JsString>
+        {
+          fn from(record: &Dataset) -> Self {
+            record.entries().into_iter().collect()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_converted_record() {
+    test_macro(
+      quote! {
+        #[opts(record = String)]
+        pub struct Headers {}
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          pub type Headers;
+        }
+
+        impl Headers {
+          /// The value of the own enumerable property `key`, or `None` if
+          /// there is none.
+          pub fn get(&self, key: &str) -> ::core::option::Option<String> {
+            self.has(key).then(|| {
+              let value =
+                ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::get(
+                  ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+                  &::wasm_bindgen::JsValue::from_str(key),
+                ));
+              ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+                <String as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value(
+                  value,
+                ),
+              )
+            })
+          }
+          /// Sets the property `key` to `value`.
+          pub fn set(&self, key: &str, value: String) {
+            ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::set(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              &::wasm_bindgen::JsValue::from_str(key),
+              &::wasm_bindgen::JsValue::from(value),
+            ));
+          }
+          /// Whether there is an own enumerable property `key`, so the
+          /// same keys as listed by `keys`.
+          pub fn has(&self, key: &str) -> bool {
+            ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Object>(self)
+              .property_is_enumerable(&::wasm_bindgen::JsValue::from_str(key))
+          }
+          /// Deletes the property `key`, returning whether it succeeded.
+          pub fn delete(&self, key: &str) -> bool {
+            ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+              ::js_sys::Reflect::delete_property(
+                ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Object>(self),
+                &::wasm_bindgen::JsValue::from_str(key),
+              ),
+            )
+          }
+          /// The keys of the own enumerable properties, from
+          /// `Object.keys`.
+          pub fn keys(&self) -> ::std::vec::Vec<::std::string::String> {
+            ::js_sys::Object::keys(::wasm_bindgen::JsCast::unchecked_ref::<
+              ::js_sys::Object,
+            >(self))
+            .iter()
+            .map(|key| ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(key.as_string()))
+            .collect()
+          }
+          /// The keys and values of the own enumerable properties, from
+          /// `Object.entries`.
+          pub fn entries(&self) -> ::std::vec::Vec<(::std::string::String, String)> {
+            ::js_sys::Object::entries(::wasm_bindgen::JsCast::unchecked_ref::<
+              ::js_sys::Object,
+            >(self))
+            .iter()
+            .map(|entry| {
+              let entry =
+                ::wasm_bindgen::JsCast::unchecked_into::<::js_sys::Array>(entry);
+              let key =
+                ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(entry.get(0).as_string());
+              let value = entry.get(1);
+              (
+                key,
+                ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+                  <String as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value(
+                    value,
+                  ),
+                ),
+              )
+            })
+            .collect()
+          }
+          /// The number of own enumerable properties.
+          pub fn len(&self) -> u32 {
+            ::js_sys::Object::keys(::wasm_bindgen::JsCast::unchecked_ref::<
+              ::js_sys::Object,
+            >(self))
+            .length()
+          }
+          /// Whether there are no own enumerable properties.
+          pub fn is_empty(&self) -> bool {
+            self.len() == 0
+          }
+        }
+
+        impl
+          ::core::convert::From<
+            ::std::collections::HashMap<::std::string::String, String>,
+          > for Headers
+        {
+          fn from(
+            map: ::std::collections::HashMap<::std::string::String, String>,
+          ) -> Self {
+            let object = ::js_sys::Object::new();
+            for (key, value) in map {
+              ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::set(
+                &object,
+                &::wasm_bindgen::JsValue::from(key),
+                &::wasm_bindgen::JsValue::from(value),
+              ));
+            }
+            ::wasm_bindgen::JsCast::unchecked_into(object)
+          }
+        }
+
+        impl ::core::convert::From<&Headers>
+          for ::std::collections::HashMap<::std::string::String, String>
+        {
+          fn from(record: &Headers) -> Self {
+            record.entries().into_iter().collect()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn struct_with_events() {
+    test_macro(
+      quote! {
+        #[opts(extends = EventTarget)]
+        #[opts(event("message", MessageEvent), event("DOMContentLoaded", Event))]
+        pub struct JsType {}
+      },
+      quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          #[wasm_bindgen(extends = EventTarget)]
+          pub type JsType;
+        }
+
+        impl JsType {
+          #[doc = "Adds a listener for the `message` event."]
+          pub fn on_message(
+            &self,
+            listener: impl FnMut(MessageEvent) + 'static,
+          ) -> JsTypeListener {
+            self.on_message_with_options(listener, &::wasm_bindgen::JsValue::UNDEFINED)
+          }
+
+          #[doc = "Adds a listener for the `message` event, with the given `addEventListener` options."]
+          pub fn on_message_with_options(
+            &self,
+            listener: impl FnMut(MessageEvent) + 'static,
+            options: &::wasm_bindgen::JsValue,
+          ) -> JsTypeListener {
+            let closure = ::wasm_bindgen::closure::Closure::<
+              dyn FnMut(MessageEvent),
+            >::new(listener);
+
+            let function = ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Function>(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(&closure),
+            )
+            .clone();
+
+            JsTypeListener::add(
+              self,
+              "message",
+              function,
+              options,
+              ::std::boxed::Box::new(closure),
+            )
+          }
+
+          #[doc = "Returns a stream of `message` events, buffered until polled. The listener is removed once the stream is dropped."]
+          pub fn events_message(
+            &self,
+          ) -> impl ::futures::Stream<Item = MessageEvent> + ::core::marker::Unpin {
+            let (sender, receiver) = ::futures::channel::mpsc::unbounded();
+
+            let listener = self
+              .on_message(move |event| {
+                let _ = sender.unbounded_send(event);
+              });
+
+            ::futures::StreamExt::map(
+              receiver,
+              move |event| {
+                let _ = &listener;
+                event
+              },
+            )
+          }
+
+          #[doc = "Adds a listener for the `DOMContentLoaded` event."]
+          pub fn on_dom_content_loaded(
+            &self,
+            listener: impl FnMut(Event) + 'static,
+          ) -> JsTypeListener {
+            self.on_dom_content_loaded_with_options(
+              listener,
+              &::wasm_bindgen::JsValue::UNDEFINED,
+            )
+          }
+
+          #[doc = "Adds a listener for the `DOMContentLoaded` event, with the given `addEventListener` options."]
+          pub fn on_dom_content_loaded_with_options(
+            &self,
+            listener: impl FnMut(Event) + 'static,
+            options: &::wasm_bindgen::JsValue,
+          ) -> JsTypeListener {
+            let closure = ::wasm_bindgen::closure::Closure::<dyn FnMut(Event)>::new(listener);
+
+            let function = ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Function>(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(&closure),
+            )
+            .clone();
+
+            JsTypeListener::add(
+              self,
+              "DOMContentLoaded",
+              function,
+              options,
+              ::std::boxed::Box::new(closure),
+            )
+          }
+
+          #[doc = "Returns a stream of `DOMContentLoaded` events, buffered until polled. The listener is removed once the stream is dropped."]
+          pub fn events_dom_content_loaded(
+            &self,
+          ) -> impl ::futures::Stream<Item = Event> + ::core::marker::Unpin {
+            let (sender, receiver) = ::futures::channel::mpsc::unbounded();
+
+            let listener = self
+              .on_dom_content_loaded(move |event| {
+                let _ = sender.unbounded_send(event);
+              });
+
+            ::futures::StreamExt::map(
+              receiver,
+              move |event| {
+                let _ = &listener;
+                event
+              },
+            )
+          }
+        }
+
+        #[doc = "Keeps an event listener added to [`JsType`] alive, removing it once dropped."]
+        #[must_use = "the event listener is removed once the guard is dropped"]
+        pub struct JsTypeListener {
+          this: JsType,
+          event: &'static str,
+          function: ::js_sys::Function,
+          options: ::wasm_bindgen::JsValue,
+          _closure: ::std::boxed::Box<dyn ::core::any::Any>,
+        }
+
+        impl JsTypeListener {
+          fn add(
+            this: &JsType,
+            event: &'static str,
+            function: ::js_sys::Function,
+            options: &::wasm_bindgen::JsValue,
+            closure: ::std::boxed::Box<dyn ::core::any::Any>,
+          ) -> Self {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "addEventListener")]
+              fn add_event_listener_js(
+                this: &JsType,
+                event: &str,
+                listener: &::js_sys::Function,
+                options: &::wasm_bindgen::JsValue,
+              );
+            }
+
+            this.add_event_listener_js(event, &function, options);
+
+            Self {
+              this: ::wasm_bindgen::JsCast::unchecked_into(
+                ::core::clone::Clone::clone(
+                  ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(this),
+                ),
+              ),
+              event,
+              function,
+              options: ::core::clone::Clone::clone(options),
+              _closure: closure,
+            }
+          }
+        }
+
+        impl ::core::ops::Drop for JsTypeListener {
+          fn drop(&mut self) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "removeEventListener")]
+              fn remove_event_listener_js(
+                this: &JsType,
+                event: &str,
+                listener: &::js_sys::Function,
+                options: &::wasm_bindgen::JsValue,
+              );
+            }
+
+            self.this.remove_event_listener_js(self.event, &self.function, &self.options);
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn simpl_impl() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn example(&self);
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example(&self) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_js(this: &JsType);
+            }
+
+            self.example_js()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_static() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn example();
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example() {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(static_method_of = JsType)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_js();
+            }
+
+            Self::example_js()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_can_map_value() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn example(&self) -> MapValue<T, U>;
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example(&self) -> U {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_js(this: &JsType) -> T;
+            }
+
+            self.example_js()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_can_async_with_args_can_map_value() {
+    test_macro(
+      quote! {
+        impl JsType {
+          async fn example(&self, a: String) -> MapValue<T, U>;
+        }
+      },
+      quote! {
+        impl JsType {
+          async fn example(&self, a: String) -> U {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              async fn example_js(this: &JsType, a: String) -> T;
+            }
+
+            self.example_js(a).await
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_can_async_with_args_can_map_value_with_block() {
+    test_macro(
+      quote! {
+        impl JsType {
+          async fn example(&self, a: String) -> MapValue<T, U> {
+            self.example_js(a).await.into()
+          }
+        }
+      },
+      quote! {
+        impl JsType {
+          async fn example(&self, a: String) -> U {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              async fn example_js(this: &JsType, a: String) -> T;
+            }
+
+            self.example_js(a).await.into()
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_can_use_nested_self_ty() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn example(
+            &self,
+            a: Option<Self>,
+          ) -> MapValue<Result<Self, JsValue>, Result<Option<Self>, JsValue>>;
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example(
+            &self,
+            a: Option<Self>,
+          ) -> Result<Option<Self>, JsValue> {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              #[wasm_bindgen(catch)]
+              fn example_js(
+                this: &JsType,
+                a: Option<JsType>,
+              ) -> Result<JsType, JsValue>;
+            }
+
+            self.example_js(a)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_arg_patterns() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn example(&self, _: u32, mut b: String, Point { x, y }: Point);
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example(&self, arg1: u32, b: String, arg3: Point) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_js(this: &JsType, arg1: u32, b: String, arg3: Point);
+            }
+
+            self.example_js(arg1, b, arg3)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_arg_patterns_with_block() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn example(Point { x, y }: Point, mut scale: u32) {
+            scale *= 2;
+            Self::example_js(Point { x: y * scale, y: x * scale }, scale)
+          }
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example(arg0: Point, mut scale: u32) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(static_method_of = JsType)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_js(arg0: Point, scale: u32);
+            }
+
+            let Point { x, y } = arg0;
+            scale *= 2;
+            Self::example_js(Point { x: y * scale, y: x * scale }, scale)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_lowers_generic_args() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn example<'a, T: Into<JsValue>, U>(
+            &'a self,
+            a: T,
+            b: U,
+            c: impl AsRef<Node>,
+          ) where
+            U: AsRef<JsType>;
+
+          fn example_2<'a, 'b, T: AsRef<Node>>(
+            &self,
+            a: &'a str,
+            b: &'b impl AsRef<Node>,
+            c: &T,
+          );
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example<'a, T: Into<JsValue>, U>(
+            &'a self,
+            a: T,
+            b: U,
+            c: impl AsRef<Node>,
+          ) where
+            U: AsRef<JsType>,
+          {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_js(this: &JsType, a: JsValue, b: &JsType, c: &Node);
+            }
+
+            self.example_js(a.into(), b.as_ref(), c.as_ref())
+          }
+
+          fn example_2<'a, 'b, T: AsRef<Node>>(
+            &self,
+            a: &'a str,
+            b: &'b impl AsRef<Node>,
+            c: &T,
+          ) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example2")]
+              fn example_2_js<'a>(this: &JsType, a: &'a str, b: &Node, c: &Node);
+            }
+
+            self.example_2_js(a, b.as_ref(), c.as_ref())
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_optional_args() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn example(&self, a: u32, #[opts(optional)] b: String);
+
+          fn example_2(
+            &self,
+            #[opts(optional = "options")] Options { .. }: Options,
+          );
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example(&self, a: u32) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_js(this: &JsType, a: u32);
+            }
+
+            self.example_js(a)
+          }
+
+          fn example_with_b(&self, a: u32, b: String) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_with_b_js(this: &JsType, a: u32, b: String);
+            }
+
+            self.example_with_b_js(a, b)
+          }
+
+          fn example_2(&self) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example2")]
+              fn example_2_js(this: &JsType);
+            }
+
+            self.example_2_js()
+          }
+
+          fn example_2_with_options(&self, arg1: Options) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example2")]
+              fn example_2_with_options_js(this: &JsType, arg1: Options);
+            }
+
+            self.example_2_with_options_js(arg1)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_overloads() {
+    test_macro(
+      quote! {
+        impl JsType {
+          #[opts(overloads)]
+          fn example(&self, a: u32, b: Option<String>, c: Option<u32>);
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example(&self, a: u32, b: Option<String>, c: Option<u32>) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_js(this: &JsType, a: u32);
+
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_with_b_js(this: &JsType, a: u32, b: Option<String>);
+
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              fn example_with_b_and_c_js(
+                this: &JsType,
+                a: u32,
+                b: Option<String>,
+                c: Option<u32>,
+              );
+            }
+
+            if c.is_some() {
+              self.example_with_b_and_c_js(a, b, c)
+            } else if b.is_some() {
+              self.example_with_b_js(a, b)
+            } else {
+              self.example_js(a)
+            }
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_overload_sets() {
+    test_macro(
+      quote! {
+        impl JsType {
+          #[opts(overload_of = "fetch")]
+          fn fetch_with_str(&self, input: &str);
+
+          /// Fetches a request.
+          #[opts(overload_of = "fetch")]
+          fn fetch_with_request(&self, input: &Request, request_init: &JsValue);
+        }
+      },
+      quote! {
+        impl JsType {
+          #[doc = "Binds to the JS `fetch(input)` overload."]
+          #[doc = ""]
+          #[doc = "Overloads of the same JS method:"]
+          #[doc = "- [`Self::fetch_with_str`]: `fetch(input)`"]
+          #[doc = "- [`Self::fetch_with_request`]: `fetch(input, requestInit)`"]
+          fn fetch_with_str(&self, input: &str) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "fetch")]
+              fn fetch_with_str_js(this: &JsType, input: &str);
+            }
+
+            self.fetch_with_str_js(input)
+          }
+
+          /// Fetches a request.
+          #[doc = ""]
+          #[doc = "Binds to the JS `fetch(input, requestInit)` overload."]
+          #[doc = ""]
+          #[doc = "Overloads of the same JS method:"]
+          #[doc = "- [`Self::fetch_with_str`]: `fetch(input)`"]
+          #[doc = "- [`Self::fetch_with_request`]: `fetch(input, requestInit)`"]
+          fn fetch_with_request(&self, input: &Request, request_init: &JsValue) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "fetch")]
+              fn fetch_with_request_js(
+                this: &JsType,
+                input: &Request,
+                request_init: &JsValue,
+              );
+            }
+
+            self.fetch_with_request_js(input, request_init)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_variadic_args() {
+    test_macro(
+      quote! {
+        impl JsType {
+          #[opts(variadic)]
+          fn example(&self, a: u32, b: impl IntoIterator<Item = String>);
+
+          fn example_2(&self, #[opts(variadic)] a: &[u32]);
+
+          #[opts(variadic)]
+          fn example_3(&self, a: &[JsValue]);
+
+          #[opts(variadic)]
+          fn example_4(&self, a: &Vec<String>);
+        }
+      },
+      quote! {
+        impl JsType {
+          fn example(&self, a: u32, b: impl IntoIterator<Item = String>) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example")]
+              #[wasm_bindgen(variadic)]
+              fn example_js(
+                this: &JsType,
+                a: u32,
+                b: ::std::boxed::Box<[::wasm_bindgen::JsValue]>,
+              );
+            }
+
+            self.example_js(
+              a,
+              b
+                .into_iter()
+                .map(::core::convert::Into::<::wasm_bindgen::JsValue>::into)
+                .collect::<::std::boxed::Box<[_]>>(),
+            )
+          }
+
+          fn example_2(&self, a: &[u32]) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example2")]
+              #[wasm_bindgen(variadic)]
+              fn example_2_js(
+                this: &JsType,
+                a: ::std::boxed::Box<[::wasm_bindgen::JsValue]>,
+              );
+            }
+
+            self.example_2_js(
+              a
+                .iter()
+                .cloned()
+                .map(::core::convert::Into::<::wasm_bindgen::JsValue>::into)
+                .collect::<::std::boxed::Box<[_]>>(),
+            )
+          }
+
+          fn example_3(&self, a: &[JsValue]) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example3")]
+              #[wasm_bindgen(variadic)]
+              fn example_3_js(this: &JsType, a: &[JsValue]);
+            }
+
+            self.example_3_js(a)
+          }
+
+          fn example_4(&self, a: &Vec<String>) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "example4")]
+              #[wasm_bindgen(variadic)]
+              fn example_4_js(
+                this: &JsType,
+                a: ::std::boxed::Box<[::wasm_bindgen::JsValue]>,
+              );
+            }
+
+            self.example_4_js(
+              a
+                .iter()
+                .cloned()
+                .map(::core::convert::Into::<::wasm_bindgen::JsValue>::into)
+                .collect::<::std::boxed::Box<[_]>>(),
+            )
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_closure_args() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn for_each(&self, f: impl FnMut(JsValue));
+
+          fn set_timeout(f: impl FnOnce() + 'static, delay: u32) -> i32;
+        }
+      },
+      quote! {
+        impl JsType {
+          fn for_each(&self, f: impl FnMut(JsValue) + 'static) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "forEach")]
+              fn for_each_js(this: &JsType, f: &::js_sys::Function);
+            }
+
+            let f = ::wasm_bindgen::closure::Closure::<dyn FnMut(JsValue)>::new(f)
+              .into_js_value();
+            self.for_each_js(::wasm_bindgen::JsCast::unchecked_ref(&f))
+          }
+
+          fn set_timeout(f: impl FnOnce() + 'static, delay: u32) -> i32 {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(static_method_of = JsType)]
+              #[wasm_bindgen(js_name = "setTimeout")]
+              fn set_timeout_js(f: &::js_sys::Function, delay: u32) -> i32;
+            }
+
+            let f = ::wasm_bindgen::closure::Closure::once_into_js(f);
+            Self::set_timeout_js(::wasm_bindgen::JsCast::unchecked_ref(&f), delay)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_guarded_closure_args() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn then(&self, #[opts(closure = guard)] f: impl Fn(u32) -> u32) -> u32;
+        }
+      },
+      quote! {
+        impl JsType {
+          fn then(
+            &self,
+            f: impl Fn(u32) -> u32 + 'static,
+          ) -> (u32, ::wasm_bindgen::closure::Closure<dyn Fn(u32) -> u32>) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "then")]
+              fn then_js(this: &JsType, f: &::js_sys::Function) -> u32;
+            }
+
+            let f = ::wasm_bindgen::closure::Closure::<dyn Fn(u32) -> u32>::new(f);
+            (
+              self
+                .then_js(
+                  ::wasm_bindgen::JsCast::unchecked_ref(
+                    ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(&f),
+                  ),
+                ),
+              f,
+            )
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_async_closure_args() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn respond_with(
+            &self,
+            f: impl FnMut(Event) -> impl Future<Output = Result<JsValue, JsValue>>,
+          );
+        }
+      },
+      quote! {
+        impl JsType {
+          fn respond_with<Fut0: Future<Output = Result<JsValue, JsValue>> + 'static>(
+            &self,
+            f: impl FnMut(Event) -> Fut0 + 'static,
+          ) {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "respondWith")]
+              fn respond_with_js(this: &JsType, f: &::js_sys::Function);
+            }
+
+            let mut f = f;
+            let f = move |arg0: Event| {
+              ::wasm_bindgen_futures::future_to_promise(f(arg0))
+            };
+            let f = ::wasm_bindgen::closure::Closure::<
+              dyn FnMut(Event) -> ::js_sys::Promise,
+            >::new(f)
+              .into_js_value();
+            self.respond_with_js(::wasm_bindgen::JsCast::unchecked_ref(&f))
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_js_fn() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn factory(&self, f: JsFn<(u32, u32), u32>) -> JsFn<(u32,), Node>;
+        }
+      },
+      quote! {
+        impl JsType {
+          fn factory(
+            &self,
+            f: impl ::core::convert::Into<JsTypeFactoryFFn>,
+          ) -> JsTypeFactoryFn {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "factory")]
+              fn factory_js(this: &JsType, f: JsTypeFactoryFFn) -> JsTypeFactoryFn;
+            }
+
+            self.factory_js(f.into())
+          }
+        }
+
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          #[doc = "A typed JS function, called with [`Self::call`]."]
+          #[wasm_bindgen(extends = ::js_sys::Function)]
+          #[wasm_bindgen(extends = ::js_sys::Object)]
+          #[wasm_bindgen(is_type_of = ::wasm_bindgen::JsValue::is_function)]
+          #[derive(Clone, Debug)]
+          type JsTypeFactoryFFn;
+        }
+
+        impl JsTypeFactoryFFn {
+          #[doc = "Calls the function, failing if it throws or returns another type."]
+          fn call(
+            &self,
+            arg0: u32,
+            arg1: u32,
+          ) -> ::core::result::Result<u32, ::wasm_bindgen::JsValue> {
+            ::js_sys::Function::apply(
+              self,
+              &::wasm_bindgen::JsValue::UNDEFINED,
+              &[
+                ::wasm_bindgen::JsValue::from(arg0),
+                ::wasm_bindgen::JsValue::from(arg1),
+              ]
+                .into_iter()
+                .collect::<::js_sys::Array>(),
+            )
+            .and_then(
+              <u32 as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+            )
+          }
+        }
+
+        impl ::core::convert::From<::js_sys::Function> for JsTypeFactoryFFn {
+          fn from(function: ::js_sys::Function) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(function)
+          }
+        }
+
+        impl<F: FnMut(u32, u32) -> u32 + 'static> ::core::convert::From<F>
+          for JsTypeFactoryFFn
+        {
+          #[doc = "Leaks the closure, so JS can call it for as long as the program runs."]
+          fn from(f: F) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(
+              ::wasm_bindgen::closure::Closure::<dyn FnMut(u32, u32) -> u32>::new(f)
+                .into_js_value(),
+            )
+          }
+        }
+
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          #[doc = "A typed JS function, called with [`Self::call`]."]
+          #[wasm_bindgen(extends = ::js_sys::Function)]
+          #[wasm_bindgen(extends = ::js_sys::Object)]
+          #[wasm_bindgen(is_type_of = ::wasm_bindgen::JsValue::is_function)]
+          #[derive(Clone, Debug)]
+          type JsTypeFactoryFn;
+        }
+
+        impl JsTypeFactoryFn {
+          #[doc = "Calls the function, failing if it throws or returns another type."]
+          fn call(
+            &self,
+            arg0: u32,
+          ) -> ::core::result::Result<Node, ::wasm_bindgen::JsValue> {
+            ::js_sys::Function::apply(
+              self,
+              &::wasm_bindgen::JsValue::UNDEFINED,
+              &[::wasm_bindgen::JsValue::from(arg0)]
+                .into_iter()
+                .collect::<::js_sys::Array>(),
+            )
+            .and_then(
+              <Node as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+            )
+          }
+        }
+
+        impl ::core::convert::From<::js_sys::Function> for JsTypeFactoryFn {
+          fn from(function: ::js_sys::Function) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(function)
+          }
+        }
+
+        impl<F: FnMut(u32) -> Node + 'static> ::core::convert::From<F>
+          for JsTypeFactoryFn
+        {
+          #[doc = "Leaks the closure, so JS can call it for as long as the program runs."]
+          fn from(f: F) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(
+              ::wasm_bindgen::closure::Closure::<dyn FnMut(u32) -> Node>::new(f)
+                .into_js_value(),
+            )
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_promise_return() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn flush(&self, after: Promise<()>) -> Promise<Self>;
+        }
+      },
+      quote! {
+        impl JsType {
+          fn flush(
+            &self,
+            after: impl ::core::convert::AsRef<::js_sys::Promise>,
+          ) -> JsTypeFlushPromise {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "flush")]
+              fn flush_js(
+                this: &JsType,
+                after: &::js_sys::Promise,
+              ) -> JsTypeFlushPromise;
+            }
+
+            self.flush_js(after.as_ref())
+          }
+        }
+
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          #[doc = "A typed JS promise, which can be awaited for `Result<JsType, JsValue>`."]
+          #[wasm_bindgen(extends = ::js_sys::Promise)]
+          #[wasm_bindgen(extends = ::js_sys::Object)]
+          #[wasm_bindgen(
+            is_type_of = ::wasm_bindgen::JsValue::is_instance_of::<::js_sys::Promise>
+          )]
+          #[derive(Clone, Debug)]
+          type JsTypeFlushPromise;
+        }
+
+        impl ::core::future::IntoFuture for JsTypeFlushPromise {
+          type Output = ::core::result::Result<JsType, ::wasm_bindgen::JsValue>;
+
+          type IntoFuture = ::core::pin::Pin<
+            ::std::boxed::Box<dyn ::core::future::Future<Output = Self::Output>>,
+          >;
+
+          fn into_future(self) -> Self::IntoFuture {
+            ::std::boxed::Box::pin(async move {
+              ::wasm_bindgen_futures::JsFuture::from(::js_sys::Promise::from(self))
+                .await
+                .and_then(
+                  <JsType as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+                )
+            })
+          }
+        }
+
+        impl ::core::convert::From<::js_sys::Promise> for JsTypeFlushPromise {
+          fn from(promise: ::js_sys::Promise) -> Self {
+            ::wasm_bindgen::JsCast::unchecked_into(promise)
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_abortable_methods() {
+    test_macro(
+      quote! {
+        impl JsType {
+          #[opts(abortable)]
+          async fn read(&self, #[opts(signal)] signal: &AbortSignal) -> JsValue;
+
+          #[opts(abortable)]
+          async fn fetch(
+            &self,
+            input: &str,
+            #[opts(signal_field = "signal")] init: &RequestInit,
+          ) -> Result<JsValue, JsValue>;
+        }
+      },
+      quote! {
+        impl JsType {
+          async fn read(&self) -> JsValue {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "read")]
+              async fn read_js(this: &JsType, signal: &AbortSignal) -> JsValue;
+            }
+
+            let (abort_guard, signal) = {
+              #[::wasm_bindgen::prelude::wasm_bindgen]
+              extern "C" {
+                type AbortController;
+
+                #[wasm_bindgen(constructor)]
+                fn new() -> AbortController;
+
+                #[wasm_bindgen(method, getter)]
+                fn signal(this: &AbortController) -> ::wasm_bindgen::JsValue;
+
+                #[wasm_bindgen(method)]
+                fn abort(this: &AbortController);
+              }
+
+              struct AbortGuard(::core::option::Option<AbortController>);
+
+              impl AbortGuard {
+                fn disarm(mut self) {
+                  self.0 = ::core::option::Option::None;
+                }
+              }
+
+              impl ::core::ops::Drop for AbortGuard {
+                fn drop(&mut self) {
+                  if let ::core::option::Option::Some(controller) = self.0.take() {
+                    controller.abort();
+                  }
+                }
+              }
+
+              let controller = AbortController::new();
+              let signal = controller.signal();
+
+              (AbortGuard(::core::option::Option::Some(controller)), signal)
+            };
+            {
+              let output = self
+                .read_js(::wasm_bindgen::JsCast::unchecked_ref(&signal))
+                .await;
+              abort_guard.disarm();
+              output
+            }
+          }
+
+          async fn fetch(
+            &self,
+            input: &str,
+            init: &RequestInit,
+          ) -> Result<JsValue, JsValue> {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "fetch")]
+              #[wasm_bindgen(catch)]
+              async fn fetch_js(
+                this: &JsType,
+                input: &str,
+                init: &RequestInit,
+              ) -> Result<JsValue, JsValue>;
+            }
+
+            let (abort_guard, signal) = {
+              #[::wasm_bindgen::prelude::wasm_bindgen]
+              extern "C" {
+                type AbortController;
+
+                #[wasm_bindgen(constructor)]
+                fn new() -> AbortController;
+
+                #[wasm_bindgen(method, getter)]
+                fn signal(this: &AbortController) -> ::wasm_bindgen::JsValue;
+
+                #[wasm_bindgen(method)]
+                fn abort(this: &AbortController);
+              }
+
+              struct AbortGuard(::core::option::Option<AbortController>);
+
+              impl AbortGuard {
+                fn disarm(mut self) {
+                  self.0 = ::core::option::Option::None;
+                }
+              }
+
+              impl ::core::ops::Drop for AbortGuard {
+                fn drop(&mut self) {
+                  if let ::core::option::Option::Some(controller) = self.0.take() {
+                    controller.abort();
+                  }
+                }
+              }
+
+              let controller = AbortController::new();
+              let signal = controller.signal();
+
+              (AbortGuard(::core::option::Option::Some(controller)), signal)
+            };
+            ::wasm_bindgen::UnwrapThrowExt::expect_throw(
+              ::js_sys::Reflect::set(
+                ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(&init),
+                &::wasm_bindgen::JsValue::from("signal"),
+                &signal,
+              ),
+              "failed to set the `AbortSignal` of the options",
+            );
+            {
+              let output = self.fetch_js(input, init).await;
+              abort_guard.disarm();
+              output
+            }
+          }
+        }
+      },
+    );
+  }
+
+  #[test]
+  fn impl_with_async_iter_return() {
+    test_macro(
+      quote! {
+        impl JsType {
+          fn values(&self) -> AsyncIter<FileSystemHandle>;
+        }
+      },
+      quote! {
+        impl JsType {
+          fn values(
+            &self,
+          ) -> impl ::futures::Stream<
+            Item = ::core::result::Result<FileSystemHandle, ::wasm_bindgen::JsValue>,
+          > + ::core::marker::Unpin {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+              #[wasm_bindgen(method)]
+              #[wasm_bindgen(js_name = "values")]
+              fn values_js(this: &JsType) -> ::wasm_bindgen::JsValue;
+            }
+
+            {
+              let iterable: ::wasm_bindgen::JsValue = self.values_js();
+
+              ::std::boxed::Box::pin(::futures::stream::try_unfold(
+                (iterable, ::core::option::Option::None::<::js_sys::AsyncIterator>),
+                |(iterable, iterator)| async move {
+                  let iterator = match iterator {
+                    ::core::option::Option::Some(iterator) => iterator,
+                    ::core::option::Option::None => {
+                      let method = ::js_sys::Reflect::get(
+                        &iterable,
+                        &::js_sys::Symbol::async_iterator(),
+                      )?;
+
+                      ::wasm_bindgen::JsCast::unchecked_into(
+                        ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Function>(&method)
+                          .call0(&iterable)?,
+                      )
+                    }
+                  };
+
+                  let next: ::js_sys::IteratorNext = ::wasm_bindgen::JsCast::unchecked_into(
+                    ::wasm_bindgen_futures::JsFuture::from(iterator.next()?).await?,
+                  );
+
+                  if next.done() {
+                    return ::core::result::Result::Ok(::core::option::Option::None);
+                  }
+
+                  let item = (<FileSystemHandle as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value)(
+                    next.value(),
+                  )?;
+
+                  ::core::result::Result::<_, ::wasm_bindgen::JsValue>::Ok(
+                    ::core::option::Option::Some((
+                      item,
+                      (iterable, ::core::option::Option::Some(iterator)),
+                    )),
+                  )
+                },
+              ))
+            }
           }
         }
       },
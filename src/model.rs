@@ -1,5 +1,6 @@
 use crate::exts::{
   IdentExt,
+  StrExt,
   TokenStreamExt,
 };
 use attribute_derive::Attribute;
@@ -64,15 +65,81 @@ pub struct Struct {
   module: Option<syn::Lit>,
   raw_module: Option<syn::Lit>,
   extends: Option<syn::Type>,
+  /// The type of the items yielded through `Symbol.iterator`.
+  iterable: Option<syn::Type>,
+  /// The type of the items accessed by index, up to `length`.
+  array_like: Option<syn::Type>,
+  /// The type of the values of an object used as a string-keyed map.
+  record: Option<syn::Type>,
+  cast: ItemCast,
+  /// The prefix of getter names, empty by default.
+  getter_prefix: String,
+  /// The prefix of setter names, `set_` by default.
+  setter_prefix: String,
+  events: Vec<Event>,
   fields: Vec<Field>,
 }
 
+/// How JS values are cast to the item type of collections.
+///
+/// Types `wasm-bindgen` converts rather than casts, e.g., `String` or
+/// numbers, are always converted with `TryFromJsValue`, which throws on
+/// other types when `unchecked`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ItemCast {
+  /// Cast without checking the type, the default.
+  Unchecked,
+  /// Converted with `TryFromJsValue`, yielding `Result<T, JsValue>`.
+  Checked,
+}
+
+impl ItemCast {
+  fn new(cast: Option<&syn::Ident>) -> Self {
+    match cast.map(ToString::to_string).as_deref() {
+      None | Some("unchecked") => Self::Unchecked,
+      Some("checked") => Self::Checked,
+      Some(_) => abort!(cast, "expected one of `unchecked` or `checked`"),
+    }
+  }
+
+  /// The type of items cast to `ty`.
+  fn item_ty(self, ty: &syn::Type) -> syn::Type {
+    match self {
+      Self::Unchecked => ty.clone(),
+      Self::Checked => parse_quote! {
+        ::core::result::Result<#ty, ::wasm_bindgen::JsValue>
+      },
+    }
+  }
+
+  /// Casts the `JsValue` produced by `value` to `ty`.
+  fn cast(self, value: TokenStream, ty: &syn::Type) -> TokenStream {
+    let try_from = quote! {
+      <#ty as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value(
+        #value,
+      )
+    };
+
+    match self {
+      Self::Unchecked if is_converted_ty(ty) => quote! {
+        ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(#try_from)
+      },
+      Self::Unchecked => quote! {
+        ::wasm_bindgen::JsCast::unchecked_into::<#ty>(#value)
+      },
+      Self::Checked => try_from,
+    }
+  }
+}
+
 impl TryFrom<syn::ItemStruct> for Struct {
   type Error = syn::Error;
 
   fn try_from(item: syn::ItemStruct) -> Result<Self, Self::Error> {
     let mut attrs = item.attrs;
 
+    let events = Event::remove_events(&mut attrs)?;
+
     let StructAttributes {
       dbg,
       on,
@@ -84,6 +151,15 @@ impl TryFrom<syn::ItemStruct> for Struct {
       js_namespace,
       module,
       raw_module,
+      iterable,
+      array_like,
+      record,
+      cast,
+      reflect,
+      has,
+      delete,
+      getter_prefix,
+      setter_prefix,
     } = StructAttributes::remove_attributes(&mut attrs)?;
 
     if getter && setter {
@@ -94,26 +170,83 @@ impl TryFrom<syn::ItemStruct> for Struct {
       );
     }
 
-    Ok(Self {
+    if let (Some(cast), None, None, None) =
+      (&cast, &iterable, &array_like, &record)
+    {
+      emit_warning!(
+        cast,
+        "`cast` has no effect without `iterable`, `array_like` or `record`"
+      );
+    }
+
+    let this = Self {
       dbg,
       attrs,
       vis: item.vis,
       name: item.ident,
       on,
       extends,
+      iterable,
+      array_like,
+      record,
+      cast: ItemCast::new(cast.as_ref()),
+      getter_prefix: name_prefix(getter_prefix.as_ref(), ""),
+      setter_prefix: name_prefix(setter_prefix.as_ref(), "set_"),
       getters: GetterKind::new(getter, setter),
       final_: r#final,
       js_name,
       js_namespace,
       module,
       raw_module,
+      events,
       fields: item
         .fields
         .into_iter()
-        .map(TryInto::try_into)
+        .map(Field::try_from)
+        .map(|field| {
+          field.map(|mut field| {
+            // Like `default` with `reflect` on the field itself
+            if let (true, Some(default)) = (reflect, &field.default) {
+              abort!(
+                default,
+                "`default` can't be used on fields of `reflect` structs";
+                help = "getters of `reflect` fields return a `Result`, \
+                        which can be defaulted with `unwrap_or`"
+              );
+            }
+
+            field.reflect |= reflect && !field.event_handler;
+            field.has |= has && field.path.is_none();
+            field.delete |= delete && field.path.is_none();
+
+            field
+          })
+        })
         .collect::<Result<Vec<_>, _>>()?,
-    })
+    };
+
+    this.check_generated_names();
+
+    Ok(this)
+  }
+}
+
+/// The value of a `getter_prefix` or `setter_prefix`, checking it can
+/// start an identifier.
+fn name_prefix(prefix: Option<&syn::LitStr>, default: &str) -> String {
+  let Some(prefix) = prefix else {
+    return default.to_owned();
+  };
+
+  let value = prefix.value();
+
+  if !value.is_empty()
+    && syn::parse_str::<syn::Ident>(&format!("{value}name")).is_err()
+  {
+    abort!(prefix, "`{}` is not a valid identifier prefix", value);
   }
+
+  value
 }
 
 impl quote::ToTokens for Struct {
@@ -126,6 +259,50 @@ impl quote::ToTokens for Struct {
       .map(|field| field.to_tokens_with_global(self))
       .collect::<TokenStream>();
 
+    let wrappers = self
+      .fields
+      .iter()
+      .map(|field| {
+        let mut wrappers = field.wrappers_with_global(self);
+
+        wrappers.extend(field.presence_fns());
+
+        wrappers
+      })
+      .chain(self.events.iter().map(|event| event.to_tokens_with_global(self)))
+      .collect::<TokenStream>();
+
+    let generated_tys = self
+      .fields
+      .iter()
+      .filter_map(|field| field.generated_ty(self))
+      .collect::<TokenStream>();
+
+    let event_handler_guard = self
+      .fields
+      .iter()
+      .any(|field| field.event_handler)
+      .then(|| self.event_handler_guard());
+
+    let listener_guard =
+      (!self.events.is_empty()).then(|| self.listener_guard());
+
+    let iterable = self.iterable.as_ref().map(|ty| self.iterable(ty));
+
+    let array_like = self.array_like.as_ref().map(|ty| self.array_like(ty));
+
+    let record = self.record.as_ref().map(|ty| self.record(ty));
+
+    let wrappers = (!wrappers.is_empty()).then(|| {
+      let ty_name = self.ty_name();
+
+      quote! {
+        impl #ty_name {
+          #wrappers
+        }
+      }
+    });
+
     let module = self
       .module
       .as_ref()
@@ -144,6 +321,20 @@ impl quote::ToTokens for Struct {
 
         #fields
       }
+
+      #wrappers
+
+      #generated_tys
+
+      #event_handler_guard
+
+      #listener_guard
+
+      #iterable
+
+      #array_like
+
+      #record
     };
 
     if self.dbg {
@@ -158,208 +349,1195 @@ impl quote::ToTokens for Struct {
 }
 
 impl Struct {
-  fn extern_type(&self) -> TokenStream {
-    let Self {
-      dbg: _,
-      attrs,
-      vis,
-      name,
-      on,
-      extends,
-      getters: _,
-      final_: _,
-      js_name,
-      js_namespace: _,
-      module: _,
-      raw_module: _,
-      fields: _,
-    } = self;
+  /// Aborts if two methods generated on the type, or two types
+  /// generated alongside it, would have the same name, e.g., when a
+  /// custom setter name is another field's getter.
+  fn check_generated_names(&self) {
+    let mut names = std::collections::HashSet::new();
+
+    for name in self.generated_names() {
+      if !names.insert(name.to_string()) {
+        abort!(
+          name,
+          "`{}` is generated more than once on this type", name;
+          help = "rename the field's methods with `getter_name` or \
+                  `setter_name`, or change `getter_prefix` or `setter_prefix`"
+        );
+      }
+    }
 
-    if on.is_some() {
-      return quote! {};
+    let mut ty_names = std::collections::HashSet::new();
+
+    for ty_name in self.generated_ty_names() {
+      if !ty_names.insert(ty_name.to_string()) {
+        abort!(
+          ty_name,
+          "`{}` is generated more than once alongside this type", ty_name;
+          help = "rename the field, as the type is named after it"
+        );
+      }
     }
+  }
 
-    let js_name = js_name
-      .as_ref()
-      .map(|js_name| quote! { #[wasm_bindgen(js_name = #js_name)] });
+  /// The names of the types generated alongside the type, in order,
+  /// spanned by what generates them.
+  fn generated_ty_names(&self) -> Vec<syn::Ident> {
+    let mut names = Vec::new();
 
-    let extends = extends
-      .as_ref()
-      .map(|extends| quote! { #[wasm_bindgen(extends = #extends)] });
+    if let Some(field) = self.fields.iter().find(|field| field.event_handler)
+    {
+      let name = self.event_handler_guard_ident();
 
-    quote! {
-      #js_name
-      #extends
-      #(#attrs)*
-      #vis type #name;
+      names.push(syn::Ident::new(&name.to_string(), field.name.span()));
     }
-  }
-}
 
-#[derive(Debug)]
-pub struct Impl {
-  attrs: Vec<syn::Attribute>,
-  ty: syn::Type,
-  options: ImplAttributes,
-  items: Vec<Method>,
-}
+    if let Some(event) = self.events.first() {
+      let name = self.listener_guard_ident();
 
-impl TryFrom<syn::ItemImpl> for Impl {
-  type Error = syn::Error;
+      names.push(syn::Ident::new(&name.to_string(), event.rust_name.span()));
+    }
 
-  fn try_from(item: syn::ItemImpl) -> Result<Self, Self::Error> {
-    let mut attrs = item.attrs.clone();
+    if let Some(ty) = self.iterable.as_ref().or(self.array_like.as_ref()) {
+      let name = self.iter_ident();
 
-    let options = ImplAttributes::remove_attributes(&mut attrs)?;
+      let span = syn::spanned::Spanned::span(ty);
 
-    Ok(Self {
-      attrs,
-      options,
-      ty: *item.self_ty.clone(),
-      items: Method::try_from_impl(item)?,
-    })
+      names.push(syn::Ident::new(&name.to_string(), span));
+    }
+
+    for field in &self.fields {
+      let suffix = if field.js_fn(self).is_some() {
+        "Fn"
+      } else if field.promise(self).is_some() {
+        "Promise"
+      } else {
+        continue;
+      };
+
+      let name = field.generated_ty_ident(self, suffix);
+
+      names.push(syn::Ident::new(&name.to_string(), field.name.span()));
+    }
+
+    names
   }
-}
 
-impl ToTokens for Impl {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
-    let Self {
-      attrs,
-      ty,
-      options,
-      items,
-    } = self;
+  /// The names of the methods generated on the type, in order.
+  fn generated_names(&self) -> Vec<syn::Ident> {
+    let mut names = Vec::new();
 
-    let output = items
-      .iter()
-      .map(|item| item.to_tokens_with_global(ty, options));
+    for field in &self.fields {
+      let name = &field.name;
 
-    let output = quote! {
-      #(#attrs)*
-      impl #ty {
-        #(#output)*
+      let getters = apply_getter_rules(self.getters, field.getters);
+
+      // Reflect fields don't have bindings
+      let has_bindings = field.has_wrappers() && !field.reflect;
+
+      if getters.is_getter() {
+        names.push(field.getter_ident(self));
+
+        if has_bindings {
+          names.push(quote::format_ident!("{name}_js"));
+        }
       }
-    };
 
-    if options.dbg {
-      emit_call_site_warning!(
-        "`#[wasm_bindgen_struct]` debug output:\n{}",
-        output.to_pretty(),
-      );
+      if getters.is_setter() {
+        names.push(field.setter_ident(self));
+
+        if has_bindings {
+          names.push(quote::format_ident!("set_{name}_js"));
+        }
+      }
+
+      if field.has {
+        names.push(quote::format_ident!("has_{name}"));
+      }
+
+      if field.delete {
+        names.push(quote::format_ident!("delete_{name}"));
+      }
     }
 
-    tokens.extend(output);
-  }
-}
+    for event in &self.events {
+      let rust_name = &event.rust_name;
 
-#[derive(Debug)]
-struct Field {
-  attrs: Vec<syn::Attribute>,
-  vis: syn::Visibility,
-  name: syn::Ident,
-  final_: bool,
-  structural: bool,
-  js_name: Option<syn::Lit>,
-  getters: GetterKind,
-  ty: syn::Type,
-}
+      names.extend([
+        quote::format_ident!("on_{rust_name}"),
+        quote::format_ident!("on_{rust_name}_with_options"),
+        quote::format_ident!("events_{rust_name}"),
+      ]);
+    }
 
-impl TryFrom<syn::Field> for Field {
-  type Error = syn::Error;
+    let collection_methods = [
+      (&self.iterable, &["iter"][..]),
+      (
+        &self.array_like,
+        &["len", "is_empty", "get", "set", "iter", "to_vec"][..],
+      ),
+      (
+        &self.record,
+        &[
+          "get", "set", "has", "delete", "keys", "entries", "len", "is_empty",
+        ][..],
+      ),
+    ];
+
+    for (ty, methods) in collection_methods {
+      let Some(ty) = ty else {
+        continue;
+      };
 
-  fn try_from(field: syn::Field) -> Result<Self, Self::Error> {
-    let mut attrs = field.attrs;
+      let span = syn::spanned::Spanned::span(ty);
 
-    let FieldAttributes {
-      getter,
-      setter,
-      final_: r#final,
-      structural,
-      js_name,
-    } = FieldAttributes::remove_attributes(&mut attrs)?;
+      names.extend(methods.iter().map(|method| syn::Ident::new(method, span)));
+    }
 
-    Ok(Self {
-      attrs,
-      vis: field.vis,
-      name: field
-        .ident
-        .unwrap_or_else(|| abort_call_site!("tuple structs are not allowed")),
-      final_: r#final,
-      structural,
-      js_name,
-      getters: GetterKind::new(getter, setter),
-      ty: field.ty,
-    })
+    names
   }
-}
 
-impl Field {
-  fn to_tokens_with_global(&self, global: &Struct) -> TokenStream {
-    let Struct {
-      dbg: _,
-      attrs: _,
-      name,
-      on,
-      getters: getters_global,
-      final_: final_global,
-      js_name: js_class,
-      js_namespace,
-      vis: _,
-      module: _,
-      raw_module: _,
-      extends: _,
-      fields: _,
-    } = global;
+  /// The type fields are bound on, either `on` or the struct itself.
+  fn ty_name(&self) -> syn::Type {
+    let name = &self.name;
 
-    let ty_name = on
-      .as_ref()
-      .map(|on| quote! { #on })
-      .unwrap_or_else(|| quote! { #name });
+    self
+      .on
+      .clone()
+      .unwrap_or_else(|| parse_quote! { #name })
+  }
 
-    let Self {
-      attrs,
-      vis,
-      name,
-      final_,
-      structural,
-      js_name,
-      getters: get_kind,
-      ty,
-    } = self;
+  fn event_handler_guard_ident(&self) -> syn::Ident {
+    quote::format_ident!("{}EventHandler", self.name)
+  }
+
+  /// The guard returned when setting an `event_handler` field.
+  fn event_handler_guard(&self) -> TokenStream {
+    let vis = &self.vis;
+
+    let ty_name = self.ty_name();
 
-    let ty = {
-      let mut ty = ty.clone();
+    let guard = self.event_handler_guard_ident();
 
-      if is_self_ty(&ty) {
-        ty = syn::parse2(ty_name.to_token_stream()).unwrap();
+    let doc = format!(
+      "Keeps an event handler set on [`{}`] alive, clearing it once \
+       dropped.",
+      ty_name.to_token_stream()
+    );
+
+    quote! {
+      #[doc = #doc]
+      #[must_use = "the event handler is cleared once the guard is dropped"]
+      #vis struct #guard {
+        this: #ty_name,
+        function: ::js_sys::Function,
+        clear: fn(&#ty_name, &::js_sys::Function),
+        _closure: ::std::boxed::Box<dyn ::core::any::Any>,
       }
 
-      ty
-    };
+      impl ::core::ops::Drop for #guard {
+        fn drop(&mut self) {
+          (self.clear)(&self.this, &self.function);
+        }
+      }
+    }
+  }
 
-    let final_ = (!*structural && (*final_global || *final_))
-      .then(|| quote! { #[wasm_bindgen(final)] });
+  fn listener_guard_ident(&self) -> syn::Ident {
+    quote::format_ident!("{}Listener", self.name)
+  }
 
-    let js_class = js_class
-      .as_ref()
-      .map(|js_class| quote! { #[wasm_bindgen(js_class = #js_class)] });
+  /// The guard returned when adding a listener for one of the `event`s.
+  fn listener_guard(&self) -> TokenStream {
+    let vis = &self.vis;
 
-    let js_name = js_name
-      .as_ref()
-      .map(|js_name| quote! { #[wasm_bindgen(js_name = #js_name)] })
-      .unwrap_or_else(|| {
-        let ident = name.to_camel_from_snake();
+    let ty_name = self.ty_name();
 
-        let ident_name = ident.to_string();
+    let guard = self.listener_guard_ident();
 
-        quote_spanned! { ident.span() => #[wasm_bindgen(js_name = #ident_name)] }
-      });
+    let doc = format!(
+      "Keeps an event listener added to [`{}`] alive, removing it once \
+       dropped.",
+      ty_name.to_token_stream()
+    );
 
-    let js_namespace = (!js_namespace.is_empty())
-      .then(|| quote! { #[wasm_bindgen(js_namespace = [#(#js_namespace),*])] });
+    quote! {
+      #[doc = #doc]
+      #[must_use = "the event listener is removed once the guard is dropped"]
+      #vis struct #guard {
+        this: #ty_name,
+        event: &'static str,
+        function: ::js_sys::Function,
+        options: ::wasm_bindgen::JsValue,
+        _closure: ::std::boxed::Box<dyn ::core::any::Any>,
+      }
 
-    let getter_fn = apply_getter_rules(*getters_global, *get_kind)
-      .is_getter()
+      impl #guard {
+        fn add(
+          this: &#ty_name,
+          event: &'static str,
+          function: ::js_sys::Function,
+          options: &::wasm_bindgen::JsValue,
+          closure: ::std::boxed::Box<dyn ::core::any::Any>,
+        ) -> Self {
+          #[::wasm_bindgen::prelude::wasm_bindgen]
+          extern "C" {
+            #[wasm_bindgen(method)]
+            #[wasm_bindgen(js_name = "addEventListener")]
+            fn add_event_listener_js(
+              this: &#ty_name,
+              event: &str,
+              listener: &::js_sys::Function,
+              options: &::wasm_bindgen::JsValue,
+            );
+          }
+
+          this.add_event_listener_js(event, &function, options);
+
+          Self {
+            this: ::wasm_bindgen::JsCast::unchecked_into(
+              ::core::clone::Clone::clone(
+                ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(this),
+              ),
+            ),
+            event,
+            function,
+            options: ::core::clone::Clone::clone(options),
+            _closure: closure,
+          }
+        }
+      }
+
+      impl ::core::ops::Drop for #guard {
+        fn drop(&mut self) {
+          #[::wasm_bindgen::prelude::wasm_bindgen]
+          extern "C" {
+            #[wasm_bindgen(method)]
+            #[wasm_bindgen(js_name = "removeEventListener")]
+            fn remove_event_listener_js(
+              this: &#ty_name,
+              event: &str,
+              listener: &::js_sys::Function,
+              options: &::wasm_bindgen::JsValue,
+            );
+          }
+
+          self.this.remove_event_listener_js(
+            self.event,
+            &self.function,
+            &self.options,
+          );
+        }
+      }
+    }
+  }
+
+  fn iter_ident(&self) -> syn::Ident {
+    quote::format_ident!("{}Iter", self.name)
+  }
+
+  /// The iterator over the items of an `iterable` type, which is
+  /// obtained through `Symbol.iterator`.
+  fn iterable(&self, ty: &syn::Type) -> TokenStream {
+    let vis = &self.vis;
+
+    let ty_name = self.ty_name();
+
+    let iter = self.iter_ident();
+
+    let mut ty = ty.clone();
+
+    replace_self_ty(&mut ty, &ty_name);
+
+    let item_ty = self.cast.item_ty(&ty);
+
+    let item = syn::Ident::new("item", proc_macro2::Span::mixed_site());
+
+    let cast = match self.cast {
+      ItemCast::Unchecked => self.cast.cast(
+        quote! {
+          ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(#item)
+        },
+        &ty,
+      ),
+      ItemCast::Checked => {
+        let cast = self.cast.cast(quote! { #item }, &ty);
+
+        quote! { #item.and_then(|#item| #cast) }
+      }
+    };
+
+    let doc = format!(
+      "Iterator over the items of [`{}`].",
+      ty_name.to_token_stream()
+    );
+
+    let not_iterable =
+      format!("`{}` is not iterable", ty_name.to_token_stream());
+
+    quote! {
+      #[doc = #doc]
+      #vis struct #iter(::js_sys::IntoIter);
+
+      impl ::core::iter::Iterator for #iter {
+        type Item = #item_ty;
+
+        fn next(&mut self) -> ::core::option::Option<Self::Item> {
+          self.0.next().map(|#item| #cast)
+        }
+      }
+
+      impl #ty_name {
+        /// Iterates over the items through `Symbol.iterator`.
+        #vis fn iter(&self) -> #iter {
+          let iter = ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+            ::js_sys::try_iter(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+            ),
+          );
+
+          #iter(::wasm_bindgen::UnwrapThrowExt::expect_throw(
+            iter,
+            #not_iterable,
+          ))
+        }
+      }
+
+      impl<'a> ::core::iter::IntoIterator for &'a #ty_name {
+        type Item = #item_ty;
+
+        type IntoIter = #iter;
+
+        fn into_iter(self) -> Self::IntoIter {
+          self.iter()
+        }
+      }
+    }
+  }
+
+  /// Index access to the items of an `array_like` type, bounded by its
+  /// `length`, and an iterator over them.
+  fn array_like(&self, ty: &syn::Type) -> TokenStream {
+    let vis = &self.vis;
+
+    let ty_name = self.ty_name();
+
+    let iter = self.iter_ident();
+
+    let mut ty = ty.clone();
+
+    replace_self_ty(&mut ty, &ty_name);
+
+    let item_ty = self.cast.item_ty(&ty);
+
+    // Items are only cast from a `JsValue` when `checked`, otherwise
+    // the binding returns `T` directly, so any `T` JS can be converted
+    // into works, e.g., the numbers of typed arrays
+    let (binding_ty, item) = match self.cast {
+      ItemCast::Unchecked => (ty.clone(), quote! { self.get_js(index) }),
+      ItemCast::Checked => (
+        parse_quote! { ::wasm_bindgen::JsValue },
+        self.cast.cast(quote! { self.get_js(index) }, &ty),
+      ),
+    };
+
+    let doc = format!(
+      "Iterator over the items of [`{}`].",
+      ty_name.to_token_stream()
+    );
+
+    quote! {
+      #[doc = #doc]
+      #vis struct #iter {
+        this: #ty_name,
+        index: u32,
+      }
+
+      impl ::core::iter::Iterator for #iter {
+        type Item = #item_ty;
+
+        fn next(&mut self) -> ::core::option::Option<Self::Item> {
+          let item = self.this.get(self.index)?;
+
+          self.index += 1;
+
+          ::core::option::Option::Some(item)
+        }
+      }
+
+      impl #ty_name {
+        /// The number of items, read from `length`.
+        #vis fn len(&self) -> u32 {
+          #[::wasm_bindgen::prelude::wasm_bindgen]
+          extern "C" {
+            #[wasm_bindgen(method, getter)]
+            #[wasm_bindgen(js_name = "length")]
+            fn length_js(this: &#ty_name) -> u32;
+          }
+
+          self.length_js()
+        }
+
+        /// Whether there are no items.
+        #vis fn is_empty(&self) -> bool {
+          self.len() == 0
+        }
+
+        /// The item at `index`, or `None` if it is out of bounds.
+        #vis fn get(&self, index: u32) -> ::core::option::Option<#item_ty> {
+          #[::wasm_bindgen::prelude::wasm_bindgen]
+          extern "C" {
+            #[wasm_bindgen(method, indexing_getter)]
+            fn get_js(this: &#ty_name, index: u32) -> #binding_ty;
+          }
+
+          (index < self.len()).then(|| #item)
+        }
+
+        /// Sets the item at `index`.
+        #vis fn set(&self, index: u32, value: #ty) {
+          #[::wasm_bindgen::prelude::wasm_bindgen]
+          extern "C" {
+            #[wasm_bindgen(method, indexing_setter)]
+            fn set_js(this: &#ty_name, index: u32, value: #ty);
+          }
+
+          self.set_js(index, value)
+        }
+
+        /// Iterates over the items, up to the `length` at each step.
+        #vis fn iter(&self) -> #iter {
+          // Extern types only implement `Clone` when they derive it
+          #iter {
+            this: ::wasm_bindgen::JsCast::unchecked_into(
+              ::core::clone::Clone::clone(
+                ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+              ),
+            ),
+            index: 0,
+          }
+        }
+
+        /// Collects the items into a `Vec`.
+        #vis fn to_vec(&self) -> ::std::vec::Vec<#item_ty> {
+          self.iter().collect()
+        }
+      }
+
+      impl<'a> ::core::iter::IntoIterator for &'a #ty_name {
+        type Item = #item_ty;
+
+        type IntoIter = #iter;
+
+        fn into_iter(self) -> Self::IntoIter {
+          self.iter()
+        }
+      }
+    }
+  }
+
+  /// Access to the own enumerable properties of a `record` type, which
+  /// is an object used as a string-keyed map, through `Reflect` and
+  /// `Object`.
+  fn record(&self, ty: &syn::Type) -> TokenStream {
+    let vis = &self.vis;
+
+    let ty_name = self.ty_name();
+
+    let mut ty = ty.clone();
+
+    replace_self_ty(&mut ty, &ty_name);
+
+    let item_ty = self.cast.item_ty(&ty);
+
+    let value = syn::Ident::new("value", proc_macro2::Span::mixed_site());
+
+    let cast = self.cast.cast(quote! { #value }, &ty);
+
+    let this = quote! {
+      ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self)
+    };
+
+    let object = quote! {
+      ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Object>(self)
+    };
+
+    // Checked values fail the whole map, rather than each entry
+    let into_map = match self.cast {
+      ItemCast::Unchecked => quote! {
+        impl ::core::convert::From<&#ty_name>
+          for ::std::collections::HashMap<::std::string::String, #ty>
+        {
+          fn from(record: &#ty_name) -> Self {
+            record.entries().into_iter().collect()
+          }
+        }
+      },
+      ItemCast::Checked => quote! {
+        impl ::core::convert::TryFrom<&#ty_name>
+          for ::std::collections::HashMap<::std::string::String, #ty>
+        {
+          type Error = ::wasm_bindgen::JsValue;
+
+          fn try_from(
+            record: &#ty_name,
+          ) -> ::core::result::Result<Self, Self::Error> {
+            record
+              .entries()
+              .into_iter()
+              .map(|(key, value)| value.map(|value| (key, value)))
+              .collect()
+          }
+        }
+      },
+    };
+
+    quote! {
+      impl #ty_name {
+        /// The value of the own enumerable property `key`, or `None` if
+        /// there is none.
+        #vis fn get(&self, key: &str) -> ::core::option::Option<#item_ty> {
+          self.has(key).then(|| {
+            let #value = ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+              ::js_sys::Reflect::get(
+                #this,
+                &::wasm_bindgen::JsValue::from_str(key),
+              ),
+            );
+
+            #cast
+          })
+        }
+
+        /// Sets the property `key` to `value`.
+        #vis fn set(&self, key: &str, value: #ty) {
+          ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::set(
+            #this,
+            &::wasm_bindgen::JsValue::from_str(key),
+            &::wasm_bindgen::JsValue::from(value),
+          ));
+        }
+
+        /// Whether there is an own enumerable property `key`, so the
+        /// same keys as listed by `keys`.
+        #vis fn has(&self, key: &str) -> bool {
+          #object.property_is_enumerable(
+            &::wasm_bindgen::JsValue::from_str(key),
+          )
+        }
+
+        /// Deletes the property `key`, returning whether it succeeded.
+        #vis fn delete(&self, key: &str) -> bool {
+          ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+            ::js_sys::Reflect::delete_property(
+              #object,
+              &::wasm_bindgen::JsValue::from_str(key),
+            ),
+          )
+        }
+
+        /// The keys of the own enumerable properties, from
+        /// `Object.keys`.
+        #vis fn keys(&self) -> ::std::vec::Vec<::std::string::String> {
+          ::js_sys::Object::keys(#object)
+            .iter()
+            .map(|key| ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+              key.as_string(),
+            ))
+            .collect()
+        }
+
+        /// The keys and values of the own enumerable properties, from
+        /// `Object.entries`.
+        #vis fn entries(
+          &self,
+        ) -> ::std::vec::Vec<(::std::string::String, #item_ty)> {
+          ::js_sys::Object::entries(#object)
+            .iter()
+            .map(|entry| {
+              let entry =
+                ::wasm_bindgen::JsCast::unchecked_into::<::js_sys::Array>(
+                  entry,
+                );
+
+              let key = ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+                entry.get(0).as_string(),
+              );
+
+              let #value = entry.get(1);
+
+              (key, #cast)
+            })
+            .collect()
+        }
+
+        /// The number of own enumerable properties.
+        #vis fn len(&self) -> u32 {
+          ::js_sys::Object::keys(#object).length()
+        }
+
+        /// Whether there are no own enumerable properties.
+        #vis fn is_empty(&self) -> bool {
+          self.len() == 0
+        }
+      }
+
+      impl ::core::convert::From<
+        ::std::collections::HashMap<::std::string::String, #ty>,
+      > for #ty_name {
+        fn from(
+          map: ::std::collections::HashMap<::std::string::String, #ty>,
+        ) -> Self {
+          let object = ::js_sys::Object::new();
+
+          for (key, value) in map {
+            ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::set(
+              &object,
+              &::wasm_bindgen::JsValue::from(key),
+              &::wasm_bindgen::JsValue::from(value),
+            ));
+          }
+
+          ::wasm_bindgen::JsCast::unchecked_into(object)
+        }
+      }
+
+      #into_map
+    }
+  }
+
+  fn extern_type(&self) -> TokenStream {
+    let Self {
+      dbg: _,
+      attrs,
+      vis,
+      name,
+      on,
+      extends,
+      getters: _,
+      final_: _,
+      js_name,
+      js_namespace: _,
+      module: _,
+      raw_module: _,
+      iterable: _,
+      array_like: _,
+      record: _,
+      cast: _,
+      getter_prefix: _,
+      setter_prefix: _,
+      events: _,
+      fields: _,
+    } = self;
+
+    if on.is_some() {
+      return quote! {};
+    }
+
+    let js_name = js_name
+      .as_ref()
+      .map(|js_name| quote! { #[wasm_bindgen(js_name = #js_name)] });
+
+    let extends = extends
+      .as_ref()
+      .map(|extends| quote! { #[wasm_bindgen(extends = #extends)] });
+
+    quote! {
+      #js_name
+      #extends
+      #(#attrs)*
+      #vis type #name;
+    }
+  }
+}
+
+/// An event declared with `#[opts(event("message", MessageEvent))]`,
+/// for which typed listeners can be added.
+///
+/// Events whose names can't be used in method names, e.g.,
+/// `app:ready`, are named with a third argument,
+/// `event("app:ready", Event, app_ready)`.
+#[derive(Debug)]
+struct Event {
+  name: syn::LitStr,
+  ty: syn::Type,
+  /// The name of the event in the generated methods, e.g.,
+  /// `dom_content_loaded` for `DOMContentLoaded`, unless given.
+  rust_name: syn::Ident,
+}
+
+impl syn::parse::Parse for Event {
+  fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+    let name = input.parse::<syn::LitStr>()?;
+
+    input.parse::<syn::Token![,]>()?;
+
+    let ty = input.parse()?;
+
+    let rust_name = if input.parse::<Option<syn::Token![,]>>()?.is_some()
+      && !input.is_empty()
+    {
+      let rust_name = input.parse()?;
+
+      input.parse::<Option<syn::Token![,]>>()?;
+
+      rust_name
+    } else {
+      let value = name.value();
+
+      let is_valid = value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && value.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+      if !is_valid {
+        abort!(
+          name,
+          "`{}` can't be used in method names", value;
+          help = "name the event with `event({:?}, Type, name)`", value
+        );
+      }
+
+      syn::Ident::new(&value.to_snake_from_camel(), name.span())
+    };
+
+    Ok(Self {
+      name,
+      ty,
+      rust_name,
+    })
+  }
+}
+
+/// An argument of `#[opts(...)]`, which is either an `event(...)`, or
+/// the tokens of any other option, up to the next comma.
+enum EventOrOption {
+  Event(Box<Event>),
+  Option(TokenStream),
+}
+
+impl syn::parse::Parse for EventOrOption {
+  fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+    syn::custom_keyword!(event);
+
+    if input.peek(event) && input.peek2(syn::token::Paren) {
+      input.parse::<event>()?;
+
+      let content;
+
+      syn::parenthesized!(content in input);
+
+      return content.parse().map(|event| Self::Event(Box::new(event)));
+    }
+
+    let mut tokens = TokenStream::new();
+
+    while !input.is_empty() && !input.peek(syn::Token![,]) {
+      tokens.extend([input.parse::<proc_macro2::TokenTree>()?]);
+    }
+
+    Ok(Self::Option(tokens))
+  }
+}
+
+impl Event {
+  /// Removes every `event(...)` from the `#[opts(...)]` attributes, as
+  /// `attribute-derive` can't parse function-like arguments.
+  fn remove_events(attrs: &mut [syn::Attribute]) -> syn::Result<Vec<Self>> {
+    let mut events = vec![];
+
+    for attr in attrs.iter_mut().filter(|attr| attr.path().is_ident("opts")) {
+      let syn::Meta::List(list) = &mut attr.meta else {
+        continue;
+      };
+
+      type Punctuated<T> = syn::punctuated::Punctuated<T, syn::Token![,]>;
+
+      let args =
+        list.parse_args_with(Punctuated::<EventOrOption>::parse_terminated)?;
+
+      let mut options = Punctuated::new();
+
+      for arg in args {
+        match arg {
+          EventOrOption::Event(event) => events.push(*event),
+          EventOrOption::Option(option) => options.push(option),
+        }
+      }
+
+      list.tokens = options.into_token_stream();
+    }
+
+    Ok(events)
+  }
+
+  /// The `on_<event>` and `on_<event>_with_options` methods.
+  fn to_tokens_with_global(&self, global: &Struct) -> TokenStream {
+    let Self {
+      name,
+      ty,
+      rust_name,
+    } = self;
+
+    let vis = &global.vis;
+
+    let guard = global.listener_guard_ident();
+
+    let on_name = quote::format_ident!("on_{rust_name}");
+
+    let on_name_with_options =
+      quote::format_ident!("on_{rust_name}_with_options");
+
+    let events_name = quote::format_ident!("events_{rust_name}");
+
+    let doc = format!("Adds a listener for the `{}` event.", name.value());
+
+    let doc_with_options = format!(
+      "Adds a listener for the `{}` event, with the given \
+       `addEventListener` options.",
+      name.value()
+    );
+
+    let doc_events = format!(
+      "Returns a stream of `{}` events, buffered until polled. The listener \
+       is removed once the stream is dropped.",
+      name.value()
+    );
+
+    quote! {
+      #[doc = #doc]
+      #vis fn #on_name(
+        &self,
+        listener: impl FnMut(#ty) + 'static,
+      ) -> #guard {
+        self.#on_name_with_options(
+          listener,
+          &::wasm_bindgen::JsValue::UNDEFINED,
+        )
+      }
+
+      #[doc = #doc_with_options]
+      #vis fn #on_name_with_options(
+        &self,
+        listener: impl FnMut(#ty) + 'static,
+        options: &::wasm_bindgen::JsValue,
+      ) -> #guard {
+        let closure =
+          ::wasm_bindgen::closure::Closure::<dyn FnMut(#ty)>::new(listener);
+
+        let function = ::wasm_bindgen::JsCast::unchecked_ref::<
+          ::js_sys::Function,
+        >(
+          ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(&closure),
+        )
+        .clone();
+
+        #guard::add(
+          self,
+          #name,
+          function,
+          options,
+          ::std::boxed::Box::new(closure),
+        )
+      }
+
+      #[doc = #doc_events]
+      #vis fn #events_name(
+        &self,
+      ) -> impl ::futures::Stream<Item = #ty> + ::core::marker::Unpin {
+        let (sender, receiver) = ::futures::channel::mpsc::unbounded();
+
+        let listener = self.#on_name(move |event| {
+          let _ = sender.unbounded_send(event);
+        });
+
+        // The stream owns the listener, so it is removed on drop
+        ::futures::StreamExt::map(receiver, move |event| {
+          let _ = &listener;
+
+          event
+        })
+      }
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct Impl {
+  attrs: Vec<syn::Attribute>,
+  ty: syn::Type,
+  options: ImplAttributes,
+  items: Vec<Method>,
+}
+
+impl TryFrom<syn::ItemImpl> for Impl {
+  type Error = syn::Error;
+
+  fn try_from(item: syn::ItemImpl) -> Result<Self, Self::Error> {
+    let mut attrs = item.attrs.clone();
+
+    let options = ImplAttributes::remove_attributes(&mut attrs)?;
+
+    let mut items = Method::try_from_impl(item.clone())?;
+
+    Method::resolve_overload_sets(&mut items, &item.self_ty);
+
+    Method::check_generated_tys(&items);
+
+    Ok(Self {
+      attrs,
+      options,
+      ty: *item.self_ty,
+      items,
+    })
+  }
+}
+
+impl ToTokens for Impl {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let Self {
+      attrs,
+      ty,
+      options,
+      items,
+    } = self;
+
+    let output = items
+      .iter()
+      .map(|item| item.to_tokens_with_global(ty, options));
+
+    // Methods expanded from `optional` arguments share their generated
+    // types
+    let mut generated_ty_names = std::collections::HashSet::new();
+
+    let generated_tys = items
+      .iter()
+      .flat_map(|item| &item.generated_tys)
+      .filter(|(name, _)| generated_ty_names.insert(name.to_string()))
+      .map(|(_, def)| def);
+
+    let output = quote! {
+      #(#attrs)*
+      impl #ty {
+        #(#output)*
+      }
+
+      #(#generated_tys)*
+    };
+
+    if options.dbg {
+      emit_call_site_warning!(
+        "`#[wasm_bindgen_struct]` debug output:\n{}",
+        output.to_pretty(),
+      );
+    }
+
+    tokens.extend(output);
+  }
+}
+
+#[derive(Debug)]
+struct Field {
+  attrs: Vec<syn::Attribute>,
+  vis: syn::Visibility,
+  name: syn::Ident,
+  final_: bool,
+  structural: bool,
+  js_name: Option<syn::Lit>,
+  getters: GetterKind,
+  event_handler: bool,
+  /// Whether the field is accessed through `Reflect` rather than bound,
+  /// so its key doesn't need to be a valid JS identifier.
+  reflect: bool,
+  /// The well-known symbol the field is keyed by, e.g., `toStringTag`.
+  symbol: Option<syn::Lit>,
+  /// The property path the field is read from, e.g., `style.color`.
+  path: Option<syn::Lit>,
+  /// Whether to generate `has_<field>()`, checking if the property is
+  /// present.
+  has: bool,
+  /// Whether to generate `delete_<field>()`, removing the property.
+  delete: bool,
+  /// The value the getter returns when the property is missing.
+  default: Option<syn::Expr>,
+  /// The type the getter returns, if not `ty`.
+  get_ty: Option<syn::Type>,
+  /// The type the setter takes, if not `ty`.
+  set_ty: Option<syn::Type>,
+  /// The name of the getter, if not `getter_prefix` and the field name.
+  getter_name: Option<syn::Ident>,
+  /// The name of the setter, if not `setter_prefix` and the field name.
+  setter_name: Option<syn::Ident>,
+  ty: syn::Type,
+}
+
+impl TryFrom<syn::Field> for Field {
+  type Error = syn::Error;
+
+  fn try_from(field: syn::Field) -> Result<Self, Self::Error> {
+    let mut attrs = field.attrs;
+
+    let FieldAttributes {
+      getter,
+      setter,
+      final_: r#final,
+      structural,
+      js_name,
+      event_handler,
+      reflect,
+      symbol,
+      path,
+      has,
+      delete,
+      default: FieldDefault(default),
+      get_ty,
+      set_ty,
+      getter_name,
+      setter_name,
+    } = FieldAttributes::remove_attributes(&mut attrs)?;
+
+    let is_marker_ty = JsFn::from_optional_ty(&field.ty).is_some()
+      || JsOptional::from_ty(&field.ty).is_some()
+      || JsPromise::from_ty(&field.ty).is_some()
+      || JsAsyncIter::from_ty(&field.ty).is_some();
+
+    if is_marker_ty && default.is_some() {
+      abort!(field.ty, "`default` can't be used with this field type");
+    }
+
+    if is_marker_ty && (get_ty.is_some() || set_ty.is_some()) {
+      abort!(
+        field.ty,
+        "`get_ty` and `set_ty` can't be used with this field type"
+      );
+    }
+
+    Ok(Self {
+      attrs,
+      vis: field.vis,
+      name: field
+        .ident
+        .unwrap_or_else(|| abort_call_site!("tuple structs are not allowed")),
+      final_: r#final,
+      structural,
+      js_name,
+      getters: GetterKind::new(getter, setter),
+      event_handler,
+      reflect: reflect || symbol.is_some() || path.is_some(),
+      symbol,
+      path,
+      has,
+      delete,
+      default,
+      get_ty,
+      set_ty,
+      getter_name,
+      setter_name,
+      ty: field.ty,
+    })
+  }
+}
+
+impl Field {
+  fn to_tokens_with_global(&self, global: &Struct) -> TokenStream {
+    let Struct {
+      dbg: _,
+      attrs: _,
+      name: _,
+      on: _,
+      getters: getters_global,
+      final_: final_global,
+      js_name: js_class,
+      js_namespace,
+      vis: _,
+      module: _,
+      raw_module: _,
+      extends: _,
+      iterable: _,
+      array_like: _,
+      record: _,
+      cast: _,
+      getter_prefix: _,
+      setter_prefix: _,
+      events: _,
+      fields: _,
+    } = global;
+
+    let ty_name = global.ty_name();
+
+    let Self {
+      attrs,
+      vis,
+      name,
+      final_,
+      structural,
+      js_name,
+      getters: get_kind,
+      event_handler: _,
+      reflect,
+      symbol: _,
+      path: _,
+      has: _,
+      delete: _,
+      default: _,
+      get_ty: _,
+      set_ty: _,
+      getter_name: _,
+      setter_name: _,
+      ty: _,
+    } = self;
+
+    // Fields accessed through `Reflect` only have wrappers
+    if *reflect {
+      return quote! {};
+    }
+
+    let final_ = (!*structural && (*final_global || *final_))
+      .then(|| quote! { #[wasm_bindgen(final)] });
+
+    let js_class = js_class
+      .as_ref()
+      .map(|js_class| quote! { #[wasm_bindgen(js_class = #js_class)] });
+
+    let js_name = if js_name.is_some() {
+      let js_name = self.js_name();
+
+      quote! { #[wasm_bindgen(js_name = #js_name)] }
+    } else {
+      let js_name = self.js_name();
+
+      quote_spanned! { js_name.span() => #[wasm_bindgen(js_name = #js_name)] }
+    };
+
+    let js_namespace = (!js_namespace.is_empty())
+      .then(|| quote! { #[wasm_bindgen(js_namespace = [#(#js_namespace),*])] });
+
+    // Fields with a wrapper are bound privately with a trailing `_js`,
+    // the wrapper takes their name, visibility and attributes instead
+    let (vis, attrs, getter_name, setter_name) = if self.has_wrappers() {
+      (
+        None,
+        &[][..],
+        quote::format_ident!("{name}_js"),
+        quote::format_ident!("set_{name}_js"),
+      )
+    } else {
+      (
+        Some(vis),
+        &attrs[..],
+        self.getter_ident(global),
+        self.setter_ident(global),
+      )
+    };
+
+    let (getter_ty, setter_ty) = if self.has_wrappers() {
+      self.binding_tys(global)
+    } else {
+      (self.getter_ty(global), self.setter_ty(global))
+    };
+
+    let getter_fn = apply_getter_rules(*getters_global, *get_kind)
+      .is_getter()
       .then(|| {
         quote! {
           #[wasm_bindgen(method, getter)]
@@ -367,32 +1545,793 @@ impl Field {
           #js_name
           #js_namespace
           #final_
-          #vis fn #name(this: &#ty_name) -> #ty;
+          #vis fn #getter_name(this: &#ty_name) -> #getter_ty;
+        }
+      });
+
+    let setter_fn = apply_getter_rules(*getters_global, *get_kind)
+      .is_setter()
+      .then(|| {
+        quote! {
+          #[wasm_bindgen(method, setter)]
+          #js_class
+          #js_name
+          #js_namespace
+          #final_
+          #vis fn #setter_name(this: &#ty_name, value: #setter_ty);
+        }
+      });
+
+    quote! {
+      #(#attrs)*
+      #getter_fn
+
+      #(#attrs)*
+      #setter_fn
+    }
+  }
+
+  /// The field type, with `Self` replaced by the type it is bound on.
+  fn ty(&self, global: &Struct) -> syn::Type {
+    let mut ty = self.ty.clone();
+
+    replace_self_ty(&mut ty, &global.ty_name());
+
+    ty
+  }
+
+  /// The type the getter returns, `get_ty` if given, with `Self`
+  /// replaced like in [`Field::ty`].
+  fn getter_ty(&self, global: &Struct) -> syn::Type {
+    let Some(get_ty) = &self.get_ty else {
+      return self.ty(global);
+    };
+
+    let mut get_ty = get_ty.clone();
+
+    replace_self_ty(&mut get_ty, &global.ty_name());
+
+    get_ty
+  }
+
+  /// The type the setter takes, `set_ty` if given, with `Self`
+  /// replaced like in [`Field::ty`].
+  fn setter_ty(&self, global: &Struct) -> syn::Type {
+    let Some(set_ty) = &self.set_ty else {
+      return self.ty(global);
+    };
+
+    let mut set_ty = set_ty.clone();
+
+    replace_self_ty(&mut set_ty, &global.ty_name());
+
+    set_ty
+  }
+
+  /// The name of the getter, `getter_name` if given.
+  fn getter_ident(&self, global: &Struct) -> syn::Ident {
+    let name = &self.name;
+
+    let prefix = &global.getter_prefix;
+
+    self
+      .getter_name
+      .clone()
+      .unwrap_or_else(|| quote::format_ident!("{prefix}{name}"))
+  }
+
+  /// The name of the setter, `setter_name` if given.
+  fn setter_ident(&self, global: &Struct) -> syn::Ident {
+    let name = &self.name;
+
+    let prefix = &global.setter_prefix;
+
+    self
+      .setter_name
+      .clone()
+      .unwrap_or_else(|| quote::format_ident!("{prefix}{name}"))
+  }
+
+  /// Whether the field needs Rust wrappers around its bindings.
+  fn has_wrappers(&self) -> bool {
+    self.reflect
+      || self.event_handler
+      || JsFn::from_optional_ty(&self.ty).is_some()
+      || JsPromise::from_ty(&self.ty).is_some()
+      || JsAsyncIter::from_ty(&self.ty).is_some()
+      || JsOptional::from_ty(&self.ty).is_some()
+      || self.default.is_some()
+  }
+
+  /// The types the getter returns and the setter takes in the
+  /// bindings of fields with wrappers.
+  fn binding_tys(&self, global: &Struct) -> (syn::Type, syn::Type) {
+    if self.event_handler {
+      (
+        parse_quote! { ::core::option::Option<::js_sys::Function> },
+        parse_quote! { ::core::option::Option<&::js_sys::Function> },
+      )
+    } else if self.promise(global).is_some() {
+      let promise_ty = self.generated_ty_ident(global, "Promise");
+
+      (parse_quote! { #promise_ty }, parse_quote! { &::js_sys::Promise })
+    } else if JsAsyncIter::from_ty(&self.ty).is_some()
+      || JsOptional::from_ty(&self.ty).is_some()
+    {
+      (
+        parse_quote! { ::wasm_bindgen::JsValue },
+        parse_quote! { &::wasm_bindgen::JsValue },
+      )
+    } else if let Some((_, optional)) = self.js_fn(global) {
+      let js_fn_ty = self.generated_ty_ident(global, "Fn");
+
+      if optional {
+        (
+          parse_quote! { ::core::option::Option<#js_fn_ty> },
+          parse_quote! { ::core::option::Option<&#js_fn_ty> },
+        )
+      } else {
+        (parse_quote! { #js_fn_ty }, parse_quote! { &#js_fn_ty })
+      }
+    } else {
+      let getter_ty = self.getter_ty(global);
+
+      (
+        parse_quote! { ::core::option::Option<#getter_ty> },
+        self.setter_ty(global),
+      )
+    }
+  }
+
+  /// The getter and setter wrappers, which go in an `impl` block
+  /// alongside the bindings.
+  fn wrappers_with_global(&self, global: &Struct) -> TokenStream {
+    if !self.has_wrappers() {
+      return quote! {};
+    }
+
+    let Self {
+      attrs,
+      getters: get_kind,
+      ..
+    } = self;
+
+    let getters = apply_getter_rules(global.getters, *get_kind);
+
+    let (getter_fn, setter_fn) = if self.reflect {
+      self.reflect_wrappers(global)
+    } else if self.event_handler {
+      self.event_handler_wrappers(global)
+    } else if self.js_fn(global).is_some() {
+      self.js_fn_wrappers(global)
+    } else if self.promise(global).is_some() {
+      self.promise_wrappers(global)
+    } else if JsOptional::from_ty(&self.ty).is_some() {
+      self.optional_wrappers(global)
+    } else if let Some(default) = &self.default {
+      self.default_wrappers(global, default)
+    } else {
+      self.typed_value_wrappers(global)
+    };
+
+    let getter_fn = getters.is_getter().then(|| {
+      quote! {
+        #(#attrs)*
+        #getter_fn
+      }
+    });
+
+    let setter_fn = getters.is_setter().then(|| {
+      quote! {
+        #(#attrs)*
+        #setter_fn
+      }
+    });
+
+    quote! {
+      #getter_fn
+
+      #setter_fn
+    }
+  }
+
+  /// The `JsFn` type of the field, and whether it is optional, unless
+  /// the field is bound in another way.
+  fn js_fn(&self, global: &Struct) -> Option<(JsFn, bool)> {
+    if self.reflect || self.event_handler {
+      return None;
+    }
+
+    JsFn::from_optional_ty(&self.ty(global))
+  }
+
+  /// The `Promise<T>` type of the field, unless the field is bound in
+  /// another way.
+  fn promise(&self, global: &Struct) -> Option<JsPromise> {
+    if self.reflect || self.event_handler {
+      return None;
+    }
+
+    JsPromise::from_ty(&self.ty(global))
+  }
+
+  /// The name of the `extern` type generated for a `JsFn` or
+  /// `Promise<T>` field, e.g., `JsTypeOnMessageFn`.
+  fn generated_ty_ident(&self, global: &Struct, suffix: &str) -> syn::Ident {
+    quote::format_ident!(
+      "{}{}{suffix}",
+      global.name,
+      self.name.to_pascal_from_snake()
+    )
+  }
+
+  /// The `extern` type generated for a `JsFn` or `Promise<T>` field,
+  /// which goes alongside the struct.
+  fn generated_ty(&self, global: &Struct) -> Option<TokenStream> {
+    if let Some((js_fn, _)) = self.js_fn(global) {
+      let ident = self.generated_ty_ident(global, "Fn");
+
+      Some(js_fn.wrapper(&self.vis, &ident))
+    } else {
+      let ident = self.generated_ty_ident(global, "Promise");
+
+      self
+        .promise(global)
+        .map(|promise| promise.wrapper(&self.vis, &ident))
+    }
+  }
+
+  /// A getter returning the typed function, and a setter taking either
+  /// the typed function or a Rust closure, which is leaked.
+  fn js_fn_wrappers(&self, global: &Struct) -> (TokenStream, TokenStream) {
+    let Self { vis, name, .. } = self;
+
+    let name_js = quote::format_ident!("{name}_js");
+
+    let getter_name = self.getter_ident(global);
+
+    let set_name = self.setter_ident(global);
+
+    let set_name_js = quote::format_ident!("set_{name}_js");
+
+    let (_, optional) = self.js_fn(global).unwrap();
+
+    let js_fn_ty = self.generated_ty_ident(global, "Fn");
+
+    let (getter_ty, _) = self.binding_tys(global);
+
+    let getter_fn = quote! {
+      #vis fn #getter_name(&self) -> #getter_ty {
+        self.#name_js()
+      }
+    };
+
+    let setter_fn = if optional {
+      quote! {
+        #vis fn #set_name(&self, value: #getter_ty) {
+          self.#set_name_js(value.as_ref())
+        }
+      }
+    } else {
+      quote! {
+        #vis fn #set_name(
+          &self,
+          value: impl ::core::convert::Into<#js_fn_ty>,
+        ) {
+          self.#set_name_js(&value.into())
+        }
+      }
+    };
+
+    (getter_fn, setter_fn)
+  }
+
+  /// A getter returning the typed promise, and a setter taking any
+  /// promise.
+  fn promise_wrappers(&self, global: &Struct) -> (TokenStream, TokenStream) {
+    let Self { vis, name, .. } = self;
+
+    let name_js = quote::format_ident!("{name}_js");
+
+    let getter_name = self.getter_ident(global);
+
+    let set_name = self.setter_ident(global);
+
+    let set_name_js = quote::format_ident!("set_{name}_js");
+
+    let (getter_ty, _) = self.binding_tys(global);
+
+    let getter_fn = quote! {
+      #vis fn #getter_name(&self) -> #getter_ty {
+        self.#name_js()
+      }
+    };
+
+    let setter_fn = quote! {
+      #vis fn #set_name(
+        &self,
+        value: impl ::core::convert::AsRef<::js_sys::Promise>,
+      ) {
+        self.#set_name_js(value.as_ref())
+      }
+    };
+
+    (getter_fn, setter_fn)
+  }
+
+  /// A getter wrapping the raw value in a typed `AsyncIter<T>`, and a
+  /// setter taking the raw value.
+  fn typed_value_wrappers(
+    &self,
+    global: &Struct,
+  ) -> (TokenStream, TokenStream) {
+    let Self { vis, name, .. } = self;
+
+    let name_js = quote::format_ident!("{name}_js");
+
+    let getter_name = self.getter_ident(global);
+
+    let set_name = self.setter_ident(global);
+
+    let set_name_js = quote::format_ident!("set_{name}_js");
+
+    let ty = self.ty(global);
+
+    let value = quote! { self.#name_js() };
+
+    let async_iter = JsAsyncIter::from_ty(&ty).unwrap();
+
+    let getter_ty = async_iter.stream_ty();
+
+    let getter_body = async_iter.stream(value);
+
+    let (_, setter_ty) = self.binding_tys(global);
+
+    let getter_fn = quote! {
+      #vis fn #getter_name(&self) -> #getter_ty {
+        #getter_body
+      }
+    };
+
+    let setter_fn = quote! {
+      #vis fn #set_name(&self, value: #setter_ty) {
+        self.#set_name_js(value)
+      }
+    };
+
+    (getter_fn, setter_fn)
+  }
+
+  /// A getter falling back to `default` when the property is missing,
+  /// and a setter taking the value as-is.
+  fn default_wrappers(
+    &self,
+    global: &Struct,
+    default: &syn::Expr,
+  ) -> (TokenStream, TokenStream) {
+    let Self { vis, name, .. } = self;
+
+    let name_js = quote::format_ident!("{name}_js");
+
+    let getter_name = self.getter_ident(global);
+
+    let set_name = self.setter_ident(global);
+
+    let set_name_js = quote::format_ident!("set_{name}_js");
+
+    let getter_ty = self.getter_ty(global);
+
+    let setter_ty = self.setter_ty(global);
+
+    let getter_fn = quote! {
+      #vis fn #getter_name(&self) -> #getter_ty {
+        self.#name_js().unwrap_or_else(|| #default)
+      }
+    };
+
+    let setter_fn = quote! {
+      #vis fn #set_name(&self, value: #setter_ty) {
+        self.#set_name_js(value)
+      }
+    };
+
+    (getter_fn, setter_fn)
+  }
+
+  /// A getter and setter converting between the raw value and the
+  /// `Option` of a `Nullable<T>`, `Undefinable<T>` or `Maybe<T>`.
+  fn optional_wrappers(&self, global: &Struct) -> (TokenStream, TokenStream) {
+    let Self { vis, name, .. } = self;
+
+    let name_js = quote::format_ident!("{name}_js");
+
+    let getter_name = self.getter_ident(global);
+
+    let set_name = self.setter_ident(global);
+
+    let set_name_js = quote::format_ident!("set_{name}_js");
+
+    let optional = JsOptional::from_ty(&self.ty(global)).unwrap();
+
+    let outer_ty = optional.outer_ty();
+
+    let read = optional.read(
+      quote! { self.#name_js() },
+      &format!("`{}` is not of the expected type", self.key().1),
+    );
+
+    let write = optional.write(quote! { value });
+
+    let getter_fn = quote! {
+      #vis fn #getter_name(&self) -> #outer_ty {
+        #read
+      }
+    };
+
+    let setter_fn = quote! {
+      #vis fn #set_name(&self, value: #outer_ty) {
+        self.#set_name_js(&#write)
+      }
+    };
+
+    (getter_fn, setter_fn)
+  }
+
+  /// A getter and setter calling `Reflect::get` and `Reflect::set` with
+  /// the key of the field, where the value read is checked to be of the
+  /// field type.
+  ///
+  /// With a `path`, the intermediate properties are walked first,
+  /// failing when one of them is `null` or `undefined`, unless it is
+  /// followed by `?.`, in which case the getter returns `None`.
+  fn reflect_wrappers(&self, global: &Struct) -> (TokenStream, TokenStream) {
+    let vis = &self.vis;
+
+    let getter_name = self.getter_ident(global);
+
+    let set_name = self.setter_ident(global);
+
+    let ty = self.getter_ty(global);
+
+    let setter_ty = self.setter_ty(global);
+
+    let mut segments = self.path_segments();
+
+    let last_segment = segments.pop();
+
+    let optional_chaining = segments.iter().any(|(_, optional)| *optional);
+
+    let (key, key_name) = if let Some((last_segment, _)) = &last_segment {
+      (quote! { #last_segment }, last_segment.clone())
+    } else {
+      self.key()
+    };
+
+    let key_name = segments
+      .iter()
+      .map(|(segment, _)| segment.as_str())
+      .chain([key_name.as_str()])
+      .collect::<Vec<_>>()
+      .join(".");
+
+    let not_set = format!("failed to set `{key_name}`");
+
+    // Reads each intermediate property in turn, with `on_nullish`
+    // returning early from the getter or setter
+    let walk = |getter: bool| {
+      let steps = segments.iter().enumerate().map(|(i, (segment, optional))| {
+        let nullish = format!(
+          "`{}` is null or undefined",
+          segments[..=i]
+            .iter()
+            .map(|(segment, _)| segment.as_str())
+            .collect::<Vec<_>>()
+            .join(".")
+        );
+
+        let on_nullish = if getter && *optional {
+          quote! { ::core::result::Result::Ok(::core::option::Option::None) }
+        } else {
+          quote! {
+            ::core::result::Result::Err(
+              ::js_sys::TypeError::new(#nullish).into(),
+            )
+          }
+        };
+
+        quote! {
+          let target = ::js_sys::Reflect::get(
+            &target,
+            &::wasm_bindgen::JsValue::from(#segment),
+          )?;
+
+          if target.is_null() || target.is_undefined() {
+            return #on_nullish;
+          }
+        }
+      });
+
+      (!segments.is_empty()).then(|| {
+        quote! {
+          let target = ::core::clone::Clone::clone(
+            ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+          );
+
+          #(#steps)*
         }
-      });
+      })
+    };
 
-    let setter_fn = apply_getter_rules(*getters_global, *get_kind)
-      .is_setter()
-      .then(|| {
-        let set_name = quote::format_ident!("set_{name}");
+    let target = if segments.is_empty() {
+      quote! { ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self) }
+    } else {
+      quote! { &target }
+    };
 
-        quote! {
-          #[wasm_bindgen(method, setter)]
-          #js_class
-          #js_name
-          #js_namespace
-          #final_
-          #vis fn #set_name(this: &#ty_name, value: #ty);
+    let (getter_ty, into_getter_ty) = if optional_chaining {
+      (
+        quote! { ::core::option::Option<#ty> },
+        Some(quote! { .map(::core::option::Option::Some) }),
+      )
+    } else {
+      (quote! { #ty }, None)
+    };
+
+    let getter_walk = walk(true);
+
+    let getter_fn = quote! {
+      #vis fn #getter_name(
+        &self,
+      ) -> ::core::result::Result<#getter_ty, ::wasm_bindgen::JsValue> {
+        #getter_walk
+
+        ::js_sys::Reflect::get(#target, &::wasm_bindgen::JsValue::from(#key))
+          .and_then(
+            <#ty as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+          )
+          #into_getter_ty
+      }
+    };
+
+    let setter_walk = walk(false);
+
+    let setter_fn = quote! {
+      #vis fn #set_name(
+        &self,
+        value: #setter_ty,
+      ) -> ::core::result::Result<(), ::wasm_bindgen::JsValue> {
+        #setter_walk
+
+        let set = ::js_sys::Reflect::set(
+          #target,
+          &::wasm_bindgen::JsValue::from(#key),
+          &::wasm_bindgen::JsValue::from(value),
+        )?;
+
+        if set {
+          ::core::result::Result::Ok(())
+        } else {
+          ::core::result::Result::Err(::js_sys::TypeError::new(#not_set).into())
         }
-      });
+      }
+    };
+
+    (getter_fn, setter_fn)
+  }
+
+  /// `has_<field>()`, using the JS `in` operator, and `delete_<field>()`,
+  /// if enabled.
+  fn presence_fns(&self) -> TokenStream {
+    let Self { vis, name, .. } = self;
+
+    let (key, key_name) = self.key();
+
+    let has_fn = self.has.then(|| {
+      let has_name = quote::format_ident!("has_{name}");
+
+      let doc = format!(
+        "Whether the `{key_name}` property is present, using the JS `in` \
+         operator."
+      );
+
+      quote! {
+        #[doc = #doc]
+        #vis fn #has_name(&self) -> bool {
+          ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(::js_sys::Reflect::has(
+            ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+            &::wasm_bindgen::JsValue::from(#key),
+          ))
+        }
+      }
+    });
+
+    let delete_fn = self.delete.then(|| {
+      let delete_name = quote::format_ident!("delete_{name}");
+
+      let doc = format!(
+        "Deletes the `{key_name}` property, returning whether it succeeded."
+      );
+
+      quote! {
+        #[doc = #doc]
+        #vis fn #delete_name(&self) -> bool {
+          ::wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+            ::js_sys::Reflect::delete_property(
+              ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Object>(self),
+              &::wasm_bindgen::JsValue::from(#key),
+            ),
+          )
+        }
+      }
+    });
 
     quote! {
-      #(#attrs)*
-      #getter_fn
+      #has_fn
 
-      #(#attrs)*
-      #setter_fn
+      #delete_fn
+    }
+  }
+
+  /// The JS name the field is bound with, either `js_name` or its name
+  /// in camel case.
+  fn js_name(&self) -> syn::Lit {
+    self.js_name.clone().unwrap_or_else(|| {
+      let ident = self.name.to_camel_from_snake();
+
+      syn::LitStr::new(&ident.to_string(), ident.span()).into()
+    })
+  }
+
+  /// The key the field is accessed with, either its well-known
+  /// `symbol` or the same JS name it is bound with, along with a
+  /// readable name for it.
+  fn key(&self) -> (TokenStream, String) {
+    if let Some(symbol) = &self.symbol {
+      let syn::Lit::Str(symbol_str) = symbol else {
+        abort!(symbol, "`symbol` must be a string literal");
+      };
+
+      let symbol_name = symbol_str.value();
+
+      let symbol_name =
+        symbol_name.strip_prefix("Symbol.").unwrap_or(&symbol_name);
+
+      let symbol_fn = match symbol_name {
+        "asyncIterator" => "async_iterator",
+        "hasInstance" => "has_instance",
+        "isConcatSpreadable" => "is_concat_spreadable",
+        "iterator" => "iterator",
+        "match" => "match_",
+        "matchAll" => "match_all",
+        "replace" => "replace",
+        "search" => "search",
+        "species" => "species",
+        "split" => "split",
+        "toPrimitive" => "to_primitive",
+        "toStringTag" => "to_string_tag",
+        "unscopables" => "unscopables",
+        _ => abort!(symbol, "`{}` is not a well-known symbol", symbol_name),
+      };
+
+      let symbol_fn = syn::Ident::new(symbol_fn, symbol.span());
+
+      (
+        quote! { ::js_sys::Symbol::#symbol_fn() },
+        format!("Symbol.{symbol_name}"),
+      )
+    } else {
+      let key = self.js_name();
+
+      let key_name = match &key {
+        syn::Lit::Str(key) => key.value(),
+        key => key.to_token_stream().to_string(),
+      };
+
+      (quote! { #key }, key_name)
+    }
+  }
+
+  /// The segments of the `path`, each with whether it is followed by
+  /// `?.`.
+  fn path_segments(&self) -> Vec<(String, bool)> {
+    let Some(path) = &self.path else {
+      return vec![];
+    };
+
+    let syn::Lit::Str(path_str) = path else {
+      abort!(path, "`path` must be a string literal");
+    };
+
+    let path_str = path_str.value();
+
+    let segments = path_str
+      .split('.')
+      .map(|segment| match segment.strip_suffix('?') {
+        Some(segment) => (segment.to_owned(), true),
+        None => (segment.to_owned(), false),
+      })
+      .collect::<Vec<_>>();
+
+    if segments.iter().any(|(segment, _)| segment.is_empty()) {
+      abort!(path, "`path` can't have empty segments");
     }
+
+    if segments.last().is_some_and(|(_, optional)| *optional) {
+      abort!(path, "`?.` must be followed by another segment");
+    }
+
+    segments
+  }
+
+  /// The raw getter, and a setter taking a Rust closure which is kept
+  /// alive by the returned guard, and cleared once it is dropped.
+  fn event_handler_wrappers(
+    &self,
+    global: &Struct,
+  ) -> (TokenStream, TokenStream) {
+    let Self { vis, name, .. } = self;
+
+    let ty_name = global.ty_name();
+
+    let event_ty = self.ty(global);
+
+    let guard = global.event_handler_guard_ident();
+
+    let name_js = quote::format_ident!("{name}_js");
+
+    let getter_name = self.getter_ident(global);
+
+    let set_name = self.setter_ident(global);
+
+    let set_name_js = quote::format_ident!("set_{name}_js");
+
+    let getter_fn = quote! {
+      #vis fn #getter_name(
+        &self,
+      ) -> ::core::option::Option<::js_sys::Function> {
+        self.#name_js()
+      }
+    };
+
+    let closure = quote! { ::wasm_bindgen::closure::Closure };
+
+    let function = quote! { ::js_sys::Function };
+
+    let some = quote! { ::core::option::Option::Some };
+
+    let setter_fn = quote! {
+      #vis fn #set_name(
+        &self,
+        handler: impl FnMut(#event_ty) + 'static,
+      ) -> #guard {
+        let closure = #closure::<dyn FnMut(#event_ty)>::new(handler);
+
+        let function = ::wasm_bindgen::JsCast::unchecked_ref::<#function>(
+          ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(&closure),
+        )
+        .clone();
+
+        self.#set_name_js(#some(&function));
+
+        #guard {
+          this: ::wasm_bindgen::JsCast::unchecked_into(
+            ::core::clone::Clone::clone(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(self),
+            ),
+          ),
+          function,
+          // Only clear the handler if it hasn't been replaced since
+          clear: |this: &#ty_name, function: &#function| {
+            if this.#name_js().as_ref() == #some(function) {
+              this.#set_name_js(::core::option::Option::None);
+            }
+          },
+          _closure: ::std::boxed::Box::new(closure),
+        }
+      }
+    };
+
+    (getter_fn, setter_fn)
   }
 }
 
@@ -456,7 +2395,7 @@ impl syn::parse::Parse for TraitItemFnWithVisibility {
   }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Method {
   attrs: Vec<syn::Attribute>,
   vis: syn::Visibility,
@@ -472,33 +2411,572 @@ struct Method {
   indexing_setter: bool,
   indexing_deleter: bool,
   variadic: bool,
+  overloads: bool,
+  overload_of: Option<syn::Lit>,
+  /// Whether dropping the returned future aborts the call through an
+  /// `AbortSignal`.
+  abortable: bool,
+  /// The number of trailing arguments marked with `#[opts(optional)]`.
+  optional: usize,
+  /// Options for each typed argument.
+  arg_attrs: Vec<ArgAttributes>,
+  /// The names and definitions of the `extern` types generated for
+  /// `JsFn` arguments, and a `JsFn` or `Promise<T>` return, which go
+  /// alongside the `impl` block.
+  generated_tys: Vec<(syn::Ident, TokenStream)>,
+}
+
+/// A typed argument of a [`Method`].
+#[derive(Debug)]
+struct MethodArg {
+  /// The ident the argument is bound to, and passed to the `_js`
+  /// binding with.
+  ident: syn::Ident,
+  /// The original pattern, if it is anything other than a plain ident,
+  /// in which case it is destructured within the method body.
+  pat: Option<syn::Pat>,
+  /// The suffix of overloads taking the argument, which is its ident
+  /// unless named with `optional = "name"`.
+  suffix: syn::Ident,
+  /// How the argument is converted before being passed to the `_js`
+  /// binding, if its type can't be used there as-is.
+  lowering: Option<ArgLowering>,
+}
+
+impl MethodArg {
+  /// The expression the argument is passed to the `_js` binding with.
+  fn forward(&self) -> TokenStream {
+    let ident = &self.ident;
+
+    match &self.lowering {
+      None => quote! { #ident },
+      Some(ArgLowering::Into(_)) => quote! { #ident.into() },
+      Some(ArgLowering::AsRef(_)) => quote! { #ident.as_ref() },
+      Some(ArgLowering::Closure {
+        policy: ClosurePolicy::Guard,
+        ..
+      }) => quote! {
+        ::wasm_bindgen::JsCast::unchecked_ref(
+          ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(&#ident)
+        )
+      },
+      Some(ArgLowering::Closure { .. }) => {
+        quote! { ::wasm_bindgen::JsCast::unchecked_ref(&#ident) }
+      }
+      Some(ArgLowering::Signal(ty)) => {
+        let signal =
+          syn::Ident::new("signal", proc_macro2::Span::mixed_site());
+
+        if matches!(ty, syn::Type::Reference(_)) {
+          quote! { ::wasm_bindgen::JsCast::unchecked_ref(&#signal) }
+        } else {
+          quote! { ::wasm_bindgen::JsCast::unchecked_into(#signal) }
+        }
+      }
+      Some(ArgLowering::Variadic { borrowed }) => {
+        let iter = if *borrowed {
+          quote! { #ident.iter().cloned() }
+        } else {
+          quote! { #ident.into_iter() }
+        };
+
+        quote! {
+          #iter
+            .map(::core::convert::Into::<::wasm_bindgen::JsValue>::into)
+            .collect::<::std::boxed::Box<[_]>>()
+        }
+      }
+    }
+  }
+
+  /// Statement turning a Rust closure into a JS callback, which must
+  /// run before calling the `_js` binding.
+  fn prelude(&self) -> Option<TokenStream> {
+    let ident = &self.ident;
+
+    let Some(ArgLowering::Closure {
+      dyn_ty,
+      kind,
+      inputs,
+      future,
+      once,
+      policy,
+    }) = &self.lowering
+    else {
+      return None;
+    };
+
+    let closure = quote! { ::wasm_bindgen::closure::Closure };
+
+    let to_promise = future.as_ref().map(|_| {
+      let params = (0..inputs.len())
+        .map(|i| {
+          quote::format_ident!(
+            "arg{i}",
+            span = proc_macro2::Span::mixed_site()
+          )
+        })
+        .collect::<Vec<_>>();
+
+      let mut_ = (kind == "FnMut").then(|| quote! { mut });
+
+      quote! {
+        let #mut_ #ident = #ident;
+        let #ident = move |#(#params: #inputs),*| {
+          ::wasm_bindgen_futures::future_to_promise(#ident(#(#params),*))
+        };
+      }
+    });
+
+    let prelude = match (policy, once) {
+      (ClosurePolicy::Leak, _) => quote! {
+        let #ident = #closure::<#dyn_ty>::new(#ident).into_js_value();
+      },
+      (ClosurePolicy::Once, _) => quote! {
+        let #ident = #closure::once_into_js(#ident);
+      },
+      (ClosurePolicy::Guard, false) => quote! {
+        let #ident = #closure::<#dyn_ty>::new(#ident);
+      },
+      (ClosurePolicy::Guard, true) => quote! {
+        let #ident = #closure::once(#ident);
+      },
+    };
+
+    Some(quote! {
+      #to_promise
+      #prelude
+    })
+  }
+
+  /// The type of the guard returned for this argument, if it is a
+  /// closure with the `guard` policy.
+  fn guard_ty(&self) -> Option<syn::Type> {
+    if let Some(ArgLowering::Closure {
+      dyn_ty,
+      policy: ClosurePolicy::Guard,
+      ..
+    }) = &self.lowering
+    {
+      Some(parse_quote! { ::wasm_bindgen::closure::Closure<#dyn_ty> })
+    } else {
+      None
+    }
+  }
+}
+
+/// The concrete, JS-facing type generic arguments are bound with.
+#[derive(Debug)]
+enum ArgLowering {
+  /// `impl Into<T>`, bound as `T`.
+  Into(syn::Type),
+  /// `impl AsRef<T>`, bound as `&T`.
+  AsRef(syn::Type),
+  /// The `variadic` argument, collected into `Box<[JsValue]>` from
+  /// either a borrowed collection, such as a slice or `&Vec<T>`, whose
+  /// items are cloned, or any `IntoIterator`.
+  Variadic { borrowed: bool },
+  /// `impl FnMut(A) -> R`, `impl Fn(A) -> R` or `impl FnOnce(A) -> R`,
+  /// wrapped in a `Closure<#dyn_ty>` and bound as `&js_sys::Function`.
+  ///
+  /// When `R` is `impl Future<Output = Result<JsValue, JsValue>>`, each
+  /// call is turned into a `Promise` instead.
+  Closure {
+    dyn_ty: syn::Type,
+    kind: syn::Ident,
+    inputs: syn::punctuated::Punctuated<syn::Type, syn::Token![,]>,
+    future: Option<syn::TypeImplTrait>,
+    once: bool,
+    policy: ClosurePolicy,
+  },
+  /// The `signal` argument of `abortable` methods, which is passed the
+  /// signal of the `AbortController` created for each call, rather than
+  /// being taken from the caller.
+  Signal(syn::Type),
+}
+
+/// How long the `Closure` created for a callback argument is kept
+/// alive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ClosurePolicy {
+  /// Leaked, so it can be called any number of times for as long as
+  /// the program runs. The default for `Fn` and `FnMut`.
+  Leak,
+  /// Returned from the method, and invalidated once dropped.
+  Guard,
+  /// Freed by JS once called. The default for `FnOnce`.
+  Once,
+}
+
+impl ArgLowering {
+  fn inner_ty(&self) -> syn::Type {
+    match self {
+      Self::Into(ty) => ty.clone(),
+      Self::AsRef(ty) => parse_quote! { &#ty },
+      Self::Variadic { .. } => {
+        parse_quote! { ::std::boxed::Box<[::wasm_bindgen::JsValue]> }
+      }
+      Self::Closure { .. } => parse_quote! { &::js_sys::Function },
+      Self::Signal(ty) => ty.clone(),
+    }
+  }
+
+  /// Finds an `Fn`, `FnMut` or `FnOnce` bound, applying the `closure`
+  /// policy if one was given.
+  fn closure<'a>(
+    bounds: impl IntoIterator<Item = &'a syn::TypeParamBound>,
+    policy: Option<&syn::Ident>,
+  ) -> Option<Self> {
+    let (kind, args) = bounds.into_iter().find_map(|bound| {
+      let syn::TypeParamBound::Trait(syn::TraitBound { path, .. }) = bound
+      else {
+        return None;
+      };
+
+      let segment = path.segments.last()?;
+
+      let syn::PathArguments::Parenthesized(args) = &segment.arguments else {
+        return None;
+      };
+
+      ["Fn", "FnMut", "FnOnce"]
+        .contains(&segment.ident.to_string().as_str())
+        .then_some((&segment.ident, args))
+    })?;
+
+    let once = kind == "FnOnce";
+
+    // `Closure::once` always produces a `dyn FnMut`
+    let dyn_kind = if once {
+      quote::format_ident!("FnMut", span = kind.span())
+    } else {
+      kind.clone()
+    };
+
+    let syn::ParenthesizedGenericArguments { inputs, output, .. } = args;
+
+    // Async callbacks return a `Promise` to JS
+    let future = match output {
+      syn::ReturnType::Type(_, ty) => match &**ty {
+        syn::Type::ImplTrait(future)
+          if future.bounds.iter().any(|bound| {
+            matches!(
+              bound,
+              syn::TypeParamBound::Trait(syn::TraitBound { path, .. })
+                if path.segments.last().is_some_and(|seg| seg.ident == "Future")
+            )
+          }) =>
+        {
+          Some(future.clone())
+        }
+        _ => None,
+      },
+      syn::ReturnType::Default => None,
+    };
+
+    let dyn_ty = if future.is_some() {
+      parse_quote! { dyn #dyn_kind(#inputs) -> ::js_sys::Promise }
+    } else {
+      parse_quote! { dyn #dyn_kind(#inputs) #output }
+    };
+
+    let policy = match policy.map(ToString::to_string).as_deref() {
+      None if once => ClosurePolicy::Once,
+      None => ClosurePolicy::Leak,
+      Some("leak") if !once => ClosurePolicy::Leak,
+      Some("once") if once => ClosurePolicy::Once,
+      Some("guard") => ClosurePolicy::Guard,
+      Some("leak") => {
+        abort!(policy, "`FnOnce` callbacks can only be `once` or `guard`")
+      }
+      Some("once") => {
+        abort!(policy, "`Fn` and `FnMut` callbacks can't be `once`");
+      }
+      Some(_) => {
+        abort!(policy, "expected one of `leak`, `guard` or `once`");
+      }
+    };
+
+    Some(Self::Closure {
+      dyn_ty,
+      kind: kind.clone(),
+      inputs: inputs.clone(),
+      future,
+      once,
+      policy,
+    })
+  }
+
+  /// `&[JsValue]` and `Box<[JsValue]>` are passed along as-is, anything
+  /// else is collected into a `Box<[JsValue]>`.
+  ///
+  /// Borrowed collections are iterated with `.iter()`, which
+  /// `&impl IntoIterator` can't be.
+  fn variadic(ty: &syn::Type) -> Option<Self> {
+    let is_js_value = |ty: &syn::Type| {
+      matches!(
+        ty,
+        syn::Type::Path(syn::TypePath { path, .. })
+          if path.segments.last().is_some_and(|seg| seg.ident == "JsValue")
+      )
+    };
+
+    match ty {
+      syn::Type::Reference(syn::TypeReference { elem, .. }) => match &**elem {
+        syn::Type::Slice(slice) if is_js_value(&slice.elem) => None,
+        syn::Type::ImplTrait(_) => abort!(
+          ty,
+          "borrowed `impl Trait` can't be `variadic`, as its items can't be \
+           cloned";
+          help = "take `impl IntoIterator` by value instead"
+        ),
+        _ => Some(Self::Variadic { borrowed: true }),
+      },
+      syn::Type::Path(syn::TypePath { path, .. })
+        if path.segments.last().is_some_and(|seg| seg.ident == "Box") =>
+      {
+        None
+      }
+      _ => Some(Self::Variadic { borrowed: false }),
+    }
+  }
+
+  /// Finds the first `Into<T>` or `AsRef<T>` bound.
+  fn from_bounds<'a>(
+    bounds: impl IntoIterator<Item = &'a syn::TypeParamBound>,
+  ) -> Option<Self> {
+    bounds.into_iter().find_map(|bound| {
+      let syn::TypeParamBound::Trait(syn::TraitBound { path, .. }) = bound
+      else {
+        return None;
+      };
+
+      let segment = path.segments.last()?;
+
+      let syn::PathArguments::AngleBracketed(
+        syn::AngleBracketedGenericArguments { args, .. },
+      ) = &segment.arguments
+      else {
+        return None;
+      };
+
+      let Some(syn::GenericArgument::Type(ty)) = args.first() else {
+        return None;
+      };
+
+      if args.len() != 1 {
+        return None;
+      }
+
+      if segment.ident == "Into" {
+        Some(Self::Into(ty.clone()))
+      } else if segment.ident == "AsRef" {
+        Some(Self::AsRef(ty.clone()))
+      } else {
+        None
+      }
+    })
+  }
 }
 
-impl TryFrom<TraitItemFnWithVisibility> for Method {
-  type Error = syn::Error;
+impl TryFrom<TraitItemFnWithVisibility> for Method {
+  type Error = syn::Error;
+
+  fn try_from(
+    TraitItemFnWithVisibility(vis, f): TraitItemFnWithVisibility,
+  ) -> Result<Self, Self::Error> {
+    let mut attrs = f.attrs;
+
+    let MethodAttributes {
+      constructor,
+      final_,
+      getter,
+      setter,
+      structural,
+      indexing_getter,
+      indexing_setter,
+      indexing_deleter,
+      js_name,
+      variadic,
+      overloads,
+      overload_of,
+      abortable,
+    } = MethodAttributes::remove_attributes(&mut attrs)?;
+
+    let mut sig = f.sig;
+
+    if let (Some(asyncness), syn::ReturnType::Type(_, ty)) =
+      (&sig.asyncness, &sig.output)
+    {
+      if JsPromise::from_ty(ty).is_some() {
+        abort!(
+          asyncness,
+          "`async fn` can't return `Promise<T>`, which is already awaitable"
+        );
+      }
+    }
+
+    // `Promise<T>` arguments are set from any promise, leaving `T`
+    // unchecked, `JsFn` ones are lowered along with the return type
+    sig.inputs.iter_mut().for_each(|arg| {
+      if let syn::FnArg::Typed(pat_ty) = arg {
+        if JsPromise::from_ty(&pat_ty.ty).is_some() {
+          *pat_ty.ty = parse_quote! {
+            impl ::core::convert::AsRef<::js_sys::Promise>
+          };
+        }
+      }
+    });
+
+    let arg_attrs = sig
+      .inputs
+      .iter_mut()
+      .filter_map(|arg| match arg {
+        syn::FnArg::Typed(pat_ty) => Some(pat_ty),
+        syn::FnArg::Receiver(_) => None,
+      })
+      .map(|pat_ty| {
+        ArgAttributes::remove_attributes(&mut pat_ty.attrs)
+          .map(|arg_attrs| (pat_ty.clone(), arg_attrs))
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let optional = arg_attrs
+      .iter()
+      .rev()
+      .take_while(|(_, arg_attrs)| arg_attrs.optional.enabled)
+      .count();
+
+    if let Some((pat_ty, _)) = arg_attrs
+      .iter()
+      .rev()
+      .skip(optional)
+      .find(|(_, arg_attrs)| arg_attrs.optional.enabled)
+    {
+      abort!(pat_ty, "only trailing arguments can be `optional`");
+    }
+
+    // Overloads are named after the optional arguments they take
+    if let Some((pat_ty, _)) = arg_attrs
+      .iter()
+      .rev()
+      .take(optional)
+      .find(|(pat_ty, arg_attrs)| {
+        pat_ident(&pat_ty.pat).is_none() && arg_attrs.optional.name.is_none()
+      })
+    {
+      abort!(
+        pat_ty.pat,
+        "`optional` patterns must be given a name for their overloads";
+        help = "name them with `#[opts(optional = \"name\")]`"
+      );
+    }
+
+    if let Some((pat_ty, _)) = arg_attrs
+      .iter()
+      .rev()
+      .skip(1)
+      .find(|(_, arg_attrs)| arg_attrs.variadic)
+    {
+      abort!(pat_ty, "only the final argument can be `variadic`");
+    }
+
+    let variadic = variadic
+      || arg_attrs.last().is_some_and(|(_, arg_attrs)| arg_attrs.variadic);
+
+    if variadic && arg_attrs.is_empty() {
+      abort!(sig.ident, "`variadic` methods must take at least one argument");
+    }
+
+    // Overloads omitting the final argument would no longer be variadic
+    if variadic && (optional != 0 || overloads) {
+      abort!(
+        sig.ident,
+        "`variadic` methods can't have `optional` arguments or `overloads`"
+      );
+    }
+
+    if overloads {
+      if optional != 0 {
+        abort!(
+          sig.ident,
+          "`overloads` can't be used together with `optional` arguments"
+        );
+      }
+
+      if !arg_attrs.last().is_some_and(|(pat_ty, _)| is_option(&pat_ty.ty)) {
+        abort!(
+          sig.ident,
+          "`overloads` requires trailing arguments of type `Option<T>`"
+        );
+      }
+
+      if let Some((pat_ty, _)) = arg_attrs
+        .iter()
+        .rev()
+        .take_while(|(pat_ty, _)| is_option(&pat_ty.ty))
+        .find(|(pat_ty, _)| pat_ident(&pat_ty.pat).is_none())
+      {
+        abort!(
+          pat_ty.pat,
+          "arguments omitted by `overloads` must be plain idents, which name \
+           the overloads"
+        );
+      }
+    }
+
+    let signal_args = arg_attrs
+      .iter()
+      .filter(|(_, arg_attrs)| {
+        arg_attrs.signal || arg_attrs.signal_field.is_some()
+      })
+      .collect::<Vec<_>>();
 
-  fn try_from(
-    TraitItemFnWithVisibility(vis, f): TraitItemFnWithVisibility,
-  ) -> Result<Self, Self::Error> {
-    let mut attrs = f.attrs;
+    if abortable {
+      if sig.asyncness.is_none() {
+        abort!(sig.ident, "`abortable` methods must be `async fn`");
+      }
 
-    let MethodAttributes {
-      constructor,
-      final_,
-      getter,
-      setter,
-      structural,
-      indexing_getter,
-      indexing_setter,
-      indexing_deleter,
-      js_name,
-      variadic,
-    } = MethodAttributes::remove_attributes(&mut attrs)?;
+      if f.default.is_some() {
+        abort!(
+          sig.ident,
+          "`abortable` methods can't have a body, as the call to JS must be \
+           generated"
+        );
+      }
 
-    Ok(Self {
+      match signal_args.as_slice() {
+        [] => abort!(
+          sig.ident,
+          "`abortable` methods must mark the argument the `AbortSignal` is \
+           passed in with `#[opts(signal)]`, or `#[opts(signal_field = \
+           \"...\")]` for a field of an options argument"
+        ),
+        [_] => {}
+        [_, (pat_ty, _), ..] => {
+          abort!(pat_ty, "only one argument can take the `AbortSignal`")
+        }
+      }
+    } else if let Some((pat_ty, _)) = signal_args.first() {
+      abort!(
+        pat_ty,
+        "`signal` and `signal_field` can only be used within `abortable` \
+         methods"
+      );
+    }
+
+    if optional != 0 && f.default.is_some() {
+      abort!(
+        sig.ident,
+        "methods with `optional` arguments can't have a body, as one method \
+         is generated for each number of arguments"
+      );
+    }
+
+    let this = Self {
       attrs,
       vis,
-      sig: f.sig,
+      sig,
       body: f.default,
       constructor,
       final_,
@@ -510,7 +2988,20 @@ impl TryFrom<TraitItemFnWithVisibility> for Method {
       indexing_setter,
       indexing_deleter,
       variadic,
-    })
+      overloads,
+      overload_of,
+      abortable,
+      optional,
+      arg_attrs: arg_attrs
+        .into_iter()
+        .map(|(_, arg_attrs)| arg_attrs)
+        .collect(),
+      generated_tys: Vec::new(),
+    };
+
+    this.check_generics();
+
+    Ok(this)
   }
 }
 
@@ -530,11 +3021,287 @@ impl Method {
       .unwrap()
       .into_iter()
       .map(TryFrom::try_from)
-      .collect::<Result<Vec<_>, _>>()?;
+      .collect::<Result<Vec<_>, _>>()?
+      .into_iter()
+      .map(|method: Self| method.lower_generated_tys(&item.self_ty))
+      .flat_map(Self::expand_optional)
+      .collect();
 
     Ok(methods)
   }
 
+  /// Replaces `JsFn` arguments, and a `JsFn` or `Promise<T>` return,
+  /// with the `extern` types generated for them, named after `ty`, the
+  /// method and the argument, e.g., `JsTypeFactoryFn` for the return of
+  /// `factory`, and `JsTypeFactoryFFn` for its `f` argument.
+  fn lower_generated_tys(mut self, ty: &syn::Type) -> Self {
+    let owner = || match ty {
+      syn::Type::Path(syn::TypePath { qself: None, path }) => {
+        path.segments.last().unwrap().ident.clone()
+      }
+      _ => abort!(
+        ty,
+        "`JsFn` and `Promise<T>` can only be used in methods of named types"
+      ),
+    };
+
+    let method = self.sig.ident.to_pascal_from_snake();
+
+    // `JsFn` arguments take either the generated type, or a Rust
+    // closure, which is leaked, just like the setters of `JsFn` fields
+    for (i, arg) in self.sig.inputs.iter_mut().enumerate() {
+      let syn::FnArg::Typed(pat_ty) = arg else {
+        continue;
+      };
+
+      // The generated type is defined outside the `impl` block, so it
+      // can't refer to `Self`
+      let mut arg_ty = (*pat_ty.ty).clone();
+
+      replace_self_ty(&mut arg_ty, ty);
+
+      let Some(js_fn) = JsFn::from_ty(&arg_ty) else {
+        continue;
+      };
+
+      let arg = pat_ident(&pat_ty.pat).map_or_else(
+        || quote::format_ident!("Arg{i}"),
+        IdentExt::to_pascal_from_snake,
+      );
+
+      let js_fn_ty = quote::format_ident!("{}{method}{arg}Fn", owner());
+
+      self
+        .generated_tys
+        .push((js_fn_ty.clone(), js_fn.wrapper(&self.vis, &js_fn_ty)));
+
+      *pat_ty.ty = parse_quote! { impl ::core::convert::Into<#js_fn_ty> };
+    }
+
+    let syn::ReturnType::Type(_, return_ty) = &mut self.sig.output else {
+      return self;
+    };
+
+    let mut lowered_ty = (**return_ty).clone();
+
+    replace_self_ty(&mut lowered_ty, ty);
+
+    if let Some((js_fn, optional)) = JsFn::from_optional_ty(&lowered_ty) {
+      let js_fn_ty = quote::format_ident!("{}{method}Fn", owner());
+
+      self
+        .generated_tys
+        .push((js_fn_ty.clone(), js_fn.wrapper(&self.vis, &js_fn_ty)));
+
+      **return_ty = if optional {
+        parse_quote! { ::core::option::Option<#js_fn_ty> }
+      } else {
+        parse_quote! { #js_fn_ty }
+      };
+    } else if let Some(promise) = JsPromise::from_ty(&lowered_ty) {
+      let promise_ty = quote::format_ident!("{}{method}Promise", owner());
+
+      self
+        .generated_tys
+        .push((promise_ty.clone(), promise.wrapper(&self.vis, &promise_ty)));
+
+      **return_ty = parse_quote! { #promise_ty };
+    }
+
+    self
+  }
+
+  /// Aborts if two methods would generate different types with the
+  /// same name, e.g., `foo_bar` and `fooBar`, which both return
+  /// `JsTypeFooBarFn`.
+  fn check_generated_tys(methods: &[Self]) {
+    let mut defs = std::collections::HashMap::new();
+
+    for method in methods {
+      for (name, def) in &method.generated_tys {
+        let def = def.to_string();
+
+        match defs.get(&name.to_string()) {
+          Some(other) if *other != def => abort!(
+            method.sig.ident,
+            "`{}` is generated more than once alongside this type", name;
+            help = "rename the method or argument, as the type is named \
+                    after them"
+          ),
+          _ => {
+            defs.insert(name.to_string(), def);
+          }
+        }
+      }
+    }
+  }
+
+  /// Binds every method with `overload_of` to the same JS method,
+  /// making sure no two overloads in a set take the same arguments,
+  /// and documents the JS signature each one maps to.
+  fn resolve_overload_sets(methods: &mut [Self], ty: &syn::Type) {
+    let js_signature = |method: &Self, js_name: &str| {
+      let args = method
+        .args()
+        .iter()
+        .map(|arg| arg.ident.to_camel_from_snake().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+      format!("{js_name}({args})")
+    };
+
+    // Overloads clash when their `_js` bindings take the same types,
+    // once lowered, regardless of lifetimes
+    let arg_tys = |method: &Self| {
+      struct EraseLifetimes;
+
+      impl syn::visit_mut::VisitMut for EraseLifetimes {
+        fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+          *lifetime = parse_quote! { '_ };
+        }
+
+        fn visit_type_reference_mut(&mut self, ty: &mut syn::TypeReference) {
+          ty.lifetime = None;
+
+          syn::visit_mut::visit_type_reference_mut(self, ty);
+        }
+      }
+
+      let receiver = method.sig.receiver().map(|receiver| {
+        let ref_ = receiver.reference.is_some().then(|| quote! { & });
+
+        quote! { #ref_ #ty }.to_string()
+      });
+
+      let pat_tys = method.sig.inputs.iter().filter_map(|arg| match arg {
+        syn::FnArg::Typed(pat_ty) => Some(pat_ty),
+        syn::FnArg::Receiver(_) => None,
+      });
+
+      let args = method.args().into_iter().zip(pat_tys).map(|(arg, pat_ty)| {
+        let mut arg_ty = arg
+          .lowering
+          .as_ref()
+          .map_or_else(|| (*pat_ty.ty).clone(), ArgLowering::inner_ty);
+
+        replace_self_ty(&mut arg_ty, ty);
+        syn::visit_mut::VisitMut::visit_type_mut(
+          &mut EraseLifetimes,
+          &mut arg_ty,
+        );
+
+        arg_ty.to_token_stream().to_string()
+      });
+
+      receiver.into_iter().chain(args).collect::<Vec<_>>()
+    };
+
+    for i in 0..methods.len() {
+      let Some(overload_of) = methods[i].overload_of.clone() else {
+        continue;
+      };
+
+      let syn::Lit::Str(js_name) = &overload_of else {
+        abort!(overload_of, "`overload_of` must be a string literal");
+      };
+
+      let js_name = js_name.value();
+
+      let set = methods
+        .iter()
+        .filter(|method| method.overload_of.as_ref() == Some(&overload_of))
+        .collect::<Vec<_>>();
+
+      if let Some(clash) = set.iter().find(|method| {
+        method.sig.ident != methods[i].sig.ident
+          && arg_tys(method) == arg_tys(&methods[i])
+      }) {
+        abort!(
+          methods[i].sig.ident,
+          "overload of `{}` clashes with `{}`, as both take the same \
+           arguments",
+          js_name,
+          clash.sig.ident,
+        );
+      }
+
+      let docs = set
+        .iter()
+        .map(|method| {
+          format!(
+            "- [`Self::{}`]: `{}`",
+            method.sig.ident,
+            js_signature(method, &js_name)
+          )
+        })
+        .collect::<Vec<_>>();
+
+      let signature = format!(
+        "Binds to the JS `{}` overload.",
+        js_signature(&methods[i], &js_name)
+      );
+
+      let method = &mut methods[i];
+
+      if method.attrs.iter().any(|attr| attr.path().is_ident("doc")) {
+        method.attrs.push(parse_quote! { #[doc = ""] });
+      }
+
+      method.attrs.push(parse_quote! { #[doc = #signature] });
+      method.attrs.push(parse_quote! { #[doc = ""] });
+      method
+        .attrs
+        .push(parse_quote! { #[doc = "Overloads of the same JS method:"] });
+      method
+        .attrs
+        .extend(docs.iter().map(|doc| parse_quote! { #[doc = #doc] }));
+
+      method.js_name = Some(overload_of);
+    }
+  }
+
+  /// Expands a method with `optional` arguments into one method for
+  /// each number of arguments, all bound to the same JS method, e.g.,
+  /// `add_event_listener` and `add_event_listener_with_options`.
+  fn expand_optional(self) -> Vec<Self> {
+    if self.optional == 0 {
+      return vec![self];
+    }
+
+    let js_name = self.js_name.clone().unwrap_or_else(|| {
+      let ident = self.sig.ident.to_camel_from_snake().to_string();
+
+      parse_quote! { #ident }
+    });
+
+    let args = self.args();
+
+    let required = self.sig.inputs.len() - self.optional;
+
+    (0..=self.optional)
+      .map(|optional| {
+        let mut method = self.clone();
+
+        method.optional = 0;
+        method.js_name = Some(js_name.clone());
+        method.sig.ident = overload_ident(
+          &self.sig.ident,
+          &args[args.len() - self.optional..][..optional],
+        );
+        method.sig.inputs = self
+          .sig
+          .inputs
+          .iter()
+          .take(required + optional)
+          .cloned()
+          .collect();
+
+        method
+      })
+      .collect()
+  }
+
   fn to_tokens_with_global(
     &self,
     ty: &syn::Type,
@@ -564,6 +3331,12 @@ impl Method {
       indexing_setter,
       indexing_deleter,
       variadic,
+      overloads: _,
+      overload_of: _,
+      abortable: _,
+      optional: _,
+      arg_attrs: _,
+      generated_tys: _,
     } = self;
 
     let module = module.as_ref().map(|module| quote! { (module = #module) });
@@ -618,9 +3391,111 @@ impl Method {
 
     let variadic = variadic.then(|| quote! { #[wasm_bindgen(variadic)] });
 
+    let args = self.args();
+
     let outer_sig = {
       let mut sig = sig.clone();
 
+      // Replace patterns which aren't plain idents with the fresh
+      // ident generated for them, they get destructured in the body
+      sig
+        .inputs
+        .iter_mut()
+        .filter_map(|arg| match arg {
+          syn::FnArg::Typed(pat_ty) => Some(pat_ty),
+          syn::FnArg::Receiver(_) => None,
+        })
+        .zip(&args)
+        .filter(|(_, arg)| arg.pat.is_some())
+        .for_each(|(pat_ty, arg)| {
+          let ident = &arg.ident;
+
+          *pat_ty.pat = parse_quote! { #ident };
+        });
+
+      // Without a body, arguments are only forwarded to the binding, so
+      // `mut` would be unused
+      if self.body.is_none() {
+        sig.inputs.iter_mut().for_each(|arg| {
+          if let syn::FnArg::Typed(syn::PatType { pat, .. }) = arg {
+            if let syn::Pat::Ident(pat_ident) = &mut **pat {
+              pat_ident.mutability = None;
+            }
+          }
+        });
+      }
+
+      // Closures must be `'static` to be passed to JS
+      sig
+        .inputs
+        .iter_mut()
+        .filter_map(|arg| match arg {
+          syn::FnArg::Typed(pat_ty) => Some(pat_ty),
+          syn::FnArg::Receiver(_) => None,
+        })
+        .zip(&args)
+        .enumerate()
+        .for_each(|(i, (pat_ty, arg))| {
+          if let (
+            Some(ArgLowering::Closure { future, .. }),
+            syn::Type::ImplTrait(syn::TypeImplTrait { bounds, .. }),
+          ) = (&arg.lowering, &mut *pat_ty.ty)
+          {
+            // `impl Trait` isn't allowed as the return type of `Fn*`
+            // bounds, so the future is made into a generic parameter
+            if let Some(future) = future {
+              let future_param = quote::format_ident!("Fut{i}");
+
+              let future_bounds = &future.bounds;
+
+              bounds
+                .iter_mut()
+                .filter_map(|bound| match bound {
+                  syn::TypeParamBound::Trait(syn::TraitBound {
+                    path, ..
+                  }) => path.segments.last_mut(),
+                  _ => None,
+                })
+                .filter_map(|segment| match &mut segment.arguments {
+                  syn::PathArguments::Parenthesized(args) => Some(args),
+                  _ => None,
+                })
+                .for_each(|args| {
+                  args.output = parse_quote! { -> #future_param };
+                });
+
+              sig.generics.params.push(parse_quote! {
+                #future_param: #future_bounds + 'static
+              });
+            }
+
+            let has_static = bounds.iter().any(|bound| {
+              matches!(
+                bound,
+                syn::TypeParamBound::Lifetime(lifetime)
+                  if lifetime.ident == "static"
+              )
+            });
+
+            if !has_static {
+              bounds.push(parse_quote! { 'static });
+            }
+          }
+        });
+
+      // The `AbortSignal` of `abortable` methods is created for each
+      // call, rather than taken from the caller
+      let mut arg_attrs = self.arg_attrs.iter();
+
+      sig.inputs = sig
+        .inputs
+        .into_iter()
+        .filter(|arg| match arg {
+          syn::FnArg::Typed(_) => !arg_attrs.next().unwrap().signal,
+          syn::FnArg::Receiver(_) => true,
+        })
+        .collect();
+
       sig.output = self.outer_return_ty();
 
       sig
@@ -646,9 +3521,84 @@ impl Method {
       let catch = is_result_from_return_ty(&sig.output)
         .then(|| quote! { #[wasm_bindgen(catch)] });
 
-      // Replace `Self` return type with the real name of the type
-      if is_self_ty_from_return_ty(&sig.output) {
-        sig.output = parse_quote! { -> #ty };
+      // Patterns aren't allowed within the `extern` block, so every
+      // argument is bound to a plain ident instead
+      //
+      // Replace `Self` anywhere within the argument and return types
+      // with the real name of the type, as `Self` is not valid
+      // within the `extern` block
+      sig
+        .inputs
+        .iter_mut()
+        .filter_map(|arg| match arg {
+          syn::FnArg::Typed(pat_ty) => Some(pat_ty),
+          syn::FnArg::Receiver(_) => None,
+        })
+        .skip(self.sig.receiver().is_some() as usize)
+        .zip(&args)
+        .for_each(|(pat_ty, arg)| {
+          let ident = &arg.ident;
+
+          *pat_ty.pat = parse_quote! { #ident };
+
+          if let Some(lowering) = &arg.lowering {
+            *pat_ty.ty = lowering.inner_ty();
+          }
+
+          replace_self_ty(&mut pat_ty.ty, ty);
+        });
+
+      // Generic arguments have been lowered to concrete types, and
+      // `extern` functions can't be generic, so only the lifetimes
+      // still used by the binding are kept
+      let used_lifetimes = used_lifetimes(&sig.inputs, &sig.output);
+
+      sig.generics.params = sig
+        .generics
+        .params
+        .into_iter()
+        .filter(|param| {
+          matches!(
+            param,
+            syn::GenericParam::Lifetime(syn::LifetimeParam { lifetime, .. })
+              if used_lifetimes.contains(lifetime)
+          )
+        })
+        .collect();
+
+      if let Some(where_clause) = &mut sig.generics.where_clause {
+        where_clause.predicates = where_clause
+          .predicates
+          .clone()
+          .into_iter()
+          .filter(|predicate| {
+            matches!(
+              predicate,
+              syn::WherePredicate::Lifetime(syn::PredicateLifetime {
+                lifetime,
+                ..
+              }) if used_lifetimes.contains(lifetime)
+            )
+          })
+          .collect();
+      }
+
+      if sig.generics.params.is_empty() {
+        sig.generics.lt_token = None;
+        sig.generics.gt_token = None;
+      }
+
+      if sig
+        .generics
+        .where_clause
+        .as_ref()
+        .is_some_and(|where_clause| where_clause.predicates.is_empty())
+      {
+        sig.generics.where_clause = None;
+      }
+
+      if let syn::ReturnType::Type(_, return_ty) = &mut sig.output {
+        replace_self_ty(return_ty, ty);
       }
 
       (catch, sig)
@@ -656,26 +3606,58 @@ impl Method {
 
     let body = self.body();
 
+    let extern_attrs = quote! {
+      #static_opt
+      #method
+      #js_class
+      #js_name
+      #js_namespace
+      #catch
+      #constructor
+      #final_
+      #getter
+      #setter
+      #indexing_getter
+      #indexing_setter
+      #indexing_deleter
+      #variadic
+    };
+
+    // `overloads` get one binding for each number of arguments, with
+    // the trailing `Option<T>` arguments omitted
+    let overloaded = self.overloaded();
+
+    let inner_sigs = (0..=overloaded).map(|optional| {
+      let mut sig = inner_sig.clone();
+
+      let omitted = overloaded - optional;
+
+      sig.ident = quote::format_ident!(
+        "{}_js",
+        overload_ident(
+          &self.sig.ident,
+          &args[args.len() - overloaded..][..optional]
+        )
+      );
+      sig.inputs = inner_sig
+        .inputs
+        .iter()
+        .take(inner_sig.inputs.len() - omitted)
+        .cloned()
+        .collect();
+
+      quote! {
+        #extern_attrs
+        #sig;
+      }
+    });
+
     quote! {
       #(#attrs)*
       #vis #outer_sig {
         #[::wasm_bindgen::prelude::wasm_bindgen #module #raw_module]
         extern "C" {
-          #static_opt
-          #method
-          #js_class
-          #js_name
-          #js_namespace
-          #catch
-          #constructor
-          #final_
-          #getter
-          #setter
-          #indexing_getter
-          #indexing_setter
-          #indexing_deleter
-          #variadic
-          #inner_sig;
+          #(#inner_sigs)*
         }
 
         #body
@@ -683,61 +3665,385 @@ impl Method {
     }
   }
 
+  /// Aborts on generic parameters which would remain in the `_js`
+  /// binding once its arguments are lowered, as `extern` functions
+  /// can't be generic.
+  fn check_generics(&self) {
+    if let Some(param) = self.sig.generics.const_params().next() {
+      abort!(param, "const generic parameters aren't supported");
+    }
+
+    // Lowering the arguments aborts on generics it can't lower
+    self.args();
+
+    if let syn::ReturnType::Type(_, ty) = self.inner_return_ty() {
+      if let Some(param) = self
+        .sig
+        .generics
+        .type_params()
+        .find(|param| contains_ident(ty.to_token_stream(), &param.ident))
+      {
+        abort!(
+          ty,
+          "`{}` can't be returned from JS, as `extern` functions can't be \
+           generic",
+          param.ident;
+          help = "return a concrete type, and convert it with `MapValue<T, U>`"
+        );
+      }
+    }
+  }
+
+  /// The number of trailing `Option<T>` arguments which are omitted
+  /// by `overloads` when `None`.
+  fn overloaded(&self) -> usize {
+    if !self.overloads {
+      return 0;
+    }
+
+    self
+      .sig
+      .inputs
+      .iter()
+      .rev()
+      .take_while(|arg| {
+        matches!(arg, syn::FnArg::Typed(pat_ty) if is_option(&pat_ty.ty))
+      })
+      .count()
+  }
+
   fn is_static(&self) -> bool {
     self.sig.receiver().is_none()
   }
 
-  fn body(&self) -> TokenStream {
-    fn fn_arg_to_ident(arg: &syn::FnArg) -> Option<syn::Ident> {
-      if let syn::FnArg::Typed(syn::PatType { pat, .. }) = arg {
-        if let syn::Pat::Ident(syn::PatIdent { ident, .. }) = &**pat {
-          Some(ident.clone())
+  /// Returns the typed arguments of this method, each bound to an
+  /// ident which can be passed along to the `_js` binding.
+  fn args(&self) -> Vec<MethodArg> {
+    self
+      .sig
+      .inputs
+      .iter()
+      .enumerate()
+      .filter_map(|(i, arg)| match arg {
+        syn::FnArg::Typed(pat_ty) => Some((i, pat_ty)),
+        syn::FnArg::Receiver(_) => None,
+      })
+      .enumerate()
+      .map(|(arg_i, (i, syn::PatType { pat, ty, .. }))| {
+        let is_last = i + 1 == self.sig.inputs.len();
+
+        let closure = self.arg_attrs[arg_i].closure.as_ref();
+
+        let lowering = if self.arg_attrs[arg_i].signal {
+          Some(ArgLowering::Signal((**ty).clone()))
+        } else if self.variadic && is_last {
+          ArgLowering::variadic(ty)
         } else {
-          abort!(pat, "only idents can be used here");
+          self.arg_lowering(ty, closure)
+        };
+
+        let (ident, pat) = match pat_ident(pat) {
+          Some(ident) => (ident.clone(), None),
+          None => (
+            quote::format_ident!(
+              "arg{i}",
+              span = proc_macro2::Span::mixed_site()
+            ),
+            Some((**pat).clone()),
+          ),
+        };
+
+        MethodArg {
+          suffix: self.arg_attrs[arg_i]
+            .optional
+            .name
+            .clone()
+            .unwrap_or_else(|| ident.clone()),
+          ident,
+          pat,
+          lowering,
         }
-      } else {
-        None
+      })
+      .collect()
+  }
+
+  /// Determines the concrete type an argument of type `ty` must be
+  /// bound with, if it is generic.
+  fn arg_lowering(
+    &self,
+    ty: &syn::Type,
+    closure: Option<&syn::Ident>,
+  ) -> Option<ArgLowering> {
+    let type_params = self.sig.generics.type_params().collect::<Vec<_>>();
+
+    let closure_abort = |closure: &syn::Ident| -> ! {
+      abort!(closure, "`closure` can only be used on `impl Fn*` arguments");
+    };
+
+    let lowering = match ty {
+      // `&impl AsRef<T>` is passed along as `&T` just like
+      // `impl AsRef<T>`, other borrowed generics can't be converted
+      syn::Type::Reference(syn::TypeReference { elem, .. })
+        if matches!(**elem, syn::Type::ImplTrait(_))
+          || matches!(
+            &**elem,
+            syn::Type::Path(syn::TypePath { qself: None, path })
+              if type_params.iter().any(|param| path.is_ident(&param.ident))
+          ) =>
+      {
+        match self.arg_lowering(elem, closure) {
+          Some(ArgLowering::AsRef(ty)) => Some(ArgLowering::AsRef(ty)),
+          _ => abort!(
+            ty,
+            "borrowed generic arguments must be bound by `AsRef<T>`, so they \
+             can be passed to JS as `&T`"
+          ),
+        }
+      }
+      syn::Type::ImplTrait(syn::TypeImplTrait { bounds, .. }) => {
+        ArgLowering::closure(bounds, closure).or_else(|| {
+          if let Some(closure) = closure {
+            closure_abort(closure);
+          }
+
+          ArgLowering::from_bounds(bounds)
+        })
+      }
+      _ if closure.is_some() => closure_abort(closure.unwrap()),
+      syn::Type::Path(syn::TypePath { qself: None, path })
+        if type_params.iter().any(|param| path.is_ident(&param.ident)) =>
+      {
+        let where_bounds = self
+          .sig
+          .generics
+          .where_clause
+          .iter()
+          .flat_map(|where_clause| &where_clause.predicates)
+          .filter_map(|predicate| match predicate {
+            syn::WherePredicate::Type(syn::PredicateType {
+              bounded_ty,
+              bounds,
+              ..
+            }) if is_ident_ty(bounded_ty, path) => Some(bounds),
+            _ => None,
+          })
+          .flatten();
+
+        let bounds = type_params
+          .iter()
+          .filter(|param| path.is_ident(&param.ident))
+          .flat_map(|param| &param.bounds)
+          .chain(where_bounds);
+
+        ArgLowering::from_bounds(bounds)
+      }
+      ty if type_params
+        .iter()
+        .any(|param| contains_ident(ty.to_token_stream(), &param.ident)) =>
+      {
+        abort!(
+          ty,
+          "generic parameters can only be used as the whole type of an \
+           argument"
+        );
       }
+      _ => return None,
+    };
+
+    if lowering.is_none() {
+      abort!(
+        ty,
+        "generic arguments must be bound by `Into<T>` or `AsRef<T>`, so they \
+         can be passed to JS as `T` or `&T`"
+      );
     }
 
+    lowering
+  }
+
+  fn body(&self) -> TokenStream {
+    let args = self.args();
+
     self
       .body
       .as_ref()
       .map(|body| {
+        let destructure = args.iter().filter_map(|arg| {
+          let ident = &arg.ident;
+
+          arg.pat.as_ref().map(|pat| quote! { let #pat = #ident; })
+        });
+
         let stmts = &body.stmts;
 
-        quote! { #(#stmts)* }
+        quote! {
+          #(#destructure)*
+          #(#stmts)*
+        }
       })
       .unwrap_or_else(|| {
         let async_ = self.sig.asyncness.as_ref().map(|_| quote! { .await });
 
-        let fn_name = quote::format_ident!("{}_js", self.sig.ident);
+        let overloaded = self.overloaded();
 
-        if self.sig.receiver().is_some() {
-          let inputs = self
-            .sig
-            .inputs
-            .clone()
-            .into_iter()
-            .skip(1)
-            .map(|arg| fn_arg_to_ident(&arg))
+        let optional_args = &args[args.len() - overloaded..];
+
+        // Call the binding taking every argument up to the last one
+        // which is `Some`
+        let calls = (0..=overloaded).rev().map(|optional| {
+          let fn_name = quote::format_ident!(
+            "{}_js",
+            overload_ident(&self.sig.ident, &optional_args[..optional])
+          );
+
+          let inputs = args
+            .iter()
+            .take(args.len() - overloaded + optional)
+            .map(MethodArg::forward)
             .collect::<syn::punctuated::Punctuated<_, syn::Token![,]>>();
 
-          quote! { self.#fn_name(#inputs) #async_ }
+          let call = if self.sig.receiver().is_some() {
+            quote! { self.#fn_name(#inputs) #async_ }
+          } else {
+            quote! { Self::#fn_name(#inputs) #async_ }
+          };
+
+          (optional_args[..optional].last(), call)
+        });
+
+        let call = calls
+          .map(|(last, call)| match last {
+            Some(MethodArg { ident, .. }) => {
+              quote! { if #ident.is_some() { #call } else }
+            }
+            None if overloaded != 0 => quote! { { #call } },
+            None => call,
+          })
+          .collect::<TokenStream>();
+
+        let preludes = args.iter().filter_map(MethodArg::prelude);
+
+        let abort_prelude = self.abortable.then(|| self.abort_prelude());
+
+        // Once the call completes, dropping the future has nothing
+        // left to abort
+        let call = if self.abortable {
+          let abort_guard =
+            syn::Ident::new("abort_guard", proc_macro2::Span::mixed_site());
+
+          quote! {
+            {
+              let output = #call;
+              #abort_guard.disarm();
+              output
+            }
+          }
         } else {
-          let inputs = &self
-            .sig
-            .inputs
-            .iter()
-            .map(fn_arg_to_ident)
-            .collect::<syn::punctuated::Punctuated<_, syn::Token![,]>>(
-          );
+          call
+        };
+
+        // Closures with the `guard` policy are returned alongside the
+        // result, so they live for as long as the caller needs them
+        let guards = args
+          .iter()
+          .filter(|arg| arg.guard_ty().is_some())
+          .map(|arg| &arg.ident)
+          .collect::<Vec<_>>();
+
+        let call = if let Some(async_iter) = self.async_iter_return_ty() {
+          async_iter.stream(call)
+        } else {
+          call
+        };
 
-          quote! { Self::#fn_name(#inputs) #async_ }
+        let call = match (guards.as_slice(), is_unit(&self.inner_return_ty())) {
+          ([], _) => call,
+          ([guard], true) => quote! { #call; #guard },
+          (guards, true) => quote! { #call; (#(#guards),*) },
+          (guards, false) => quote! { (#call, #(#guards),*) },
+        };
+
+        quote! {
+          #abort_prelude
+          #(#preludes)*
+          #call
         }
       })
   }
 
+  /// Creates the `AbortController` of an `abortable` method, along
+  /// with a guard aborting it once dropped, and passes its signal to
+  /// the `signal_field` of an options argument if needed.
+  fn abort_prelude(&self) -> TokenStream {
+    let abort_guard =
+      syn::Ident::new("abort_guard", proc_macro2::Span::mixed_site());
+
+    let signal = syn::Ident::new("signal", proc_macro2::Span::mixed_site());
+
+    let signal_field = self
+      .args()
+      .iter()
+      .zip(&self.arg_attrs)
+      .find_map(|(arg, arg_attrs)| {
+        arg_attrs.signal_field.as_ref().map(|field| (&arg.ident, field))
+      })
+      .map(|(ident, field)| {
+        quote! {
+          ::wasm_bindgen::UnwrapThrowExt::expect_throw(
+            ::js_sys::Reflect::set(
+              ::core::convert::AsRef::<::wasm_bindgen::JsValue>::as_ref(
+                &#ident
+              ),
+              &::wasm_bindgen::JsValue::from(#field),
+              &#signal,
+            ),
+            "failed to set the `AbortSignal` of the options",
+          );
+        }
+      });
+
+    quote! {
+      let (#abort_guard, #signal) = {
+        #[::wasm_bindgen::prelude::wasm_bindgen]
+        extern "C" {
+          type AbortController;
+
+          #[wasm_bindgen(constructor)]
+          fn new() -> AbortController;
+
+          #[wasm_bindgen(method, getter)]
+          fn signal(this: &AbortController) -> ::wasm_bindgen::JsValue;
+
+          #[wasm_bindgen(method)]
+          fn abort(this: &AbortController);
+        }
+
+        struct AbortGuard(::core::option::Option<AbortController>);
+
+        impl AbortGuard {
+          fn disarm(mut self) {
+            self.0 = ::core::option::Option::None;
+          }
+        }
+
+        impl ::core::ops::Drop for AbortGuard {
+          fn drop(&mut self) {
+            if let ::core::option::Option::Some(controller) = self.0.take() {
+              controller.abort();
+            }
+          }
+        }
+
+        let controller = AbortController::new();
+        let signal = controller.signal();
+
+        (AbortGuard(::core::option::Option::Some(controller)), signal)
+      };
+
+      #signal_field
+    }
+  }
+
   fn map_value_types(&self) -> Option<(syn::Type, syn::Type)> {
     if let syn::ReturnType::Type(_, ty) = &self.sig.output {
       if let syn::Type::Path(syn::TypePath {
@@ -782,6 +4088,14 @@ impl Method {
     }
   }
 
+  fn async_iter_return_ty(&self) -> Option<JsAsyncIter> {
+    if let syn::ReturnType::Type(_, ty) = &self.sig.output {
+      JsAsyncIter::from_ty(ty)
+    } else {
+      None
+    }
+  }
+
   fn inner_return_ty(&self) -> syn::ReturnType {
     let mut return_ty = self.sig.output.clone();
 
@@ -791,6 +4105,10 @@ impl Method {
       }
     }
 
+    if self.async_iter_return_ty().is_some() {
+      return_ty = parse_quote! { -> ::wasm_bindgen::JsValue };
+    }
+
     return_ty
   }
 
@@ -803,6 +4121,33 @@ impl Method {
       }
     }
 
+    if let Some(async_iter) = self.async_iter_return_ty() {
+      let stream_ty = async_iter.stream_ty();
+
+      return_ty = parse_quote! { -> #stream_ty };
+    }
+
+    // The generated body returns the guards of `guard` closures
+    // alongside the result
+    if self.body.is_none() {
+      let guard_tys =
+        self.args().iter().filter_map(MethodArg::guard_ty).collect::<Vec<_>>();
+
+      return_ty = match (guard_tys.as_slice(), &return_ty) {
+        ([], _) => return_ty,
+        ([guard_ty], return_ty) if is_unit(return_ty) => {
+          parse_quote! { -> #guard_ty }
+        }
+        (guard_tys, return_ty) if is_unit(return_ty) => {
+          parse_quote! { -> (#(#guard_tys),*) }
+        }
+        (guard_tys, syn::ReturnType::Type(_, ty)) => {
+          parse_quote! { -> (#ty, #(#guard_tys),*) }
+        }
+        (_, syn::ReturnType::Default) => unreachable!(),
+      };
+    }
+
     return_ty
   }
 }
@@ -825,6 +4170,18 @@ struct StructAttributes {
   module: Option<syn::Lit>,
   #[attribute(conflicts = [module])]
   raw_module: Option<syn::Lit>,
+  #[attribute(conflicts = [array_like, record])]
+  iterable: Option<syn::Type>,
+  #[attribute(conflicts = [iterable, record])]
+  array_like: Option<syn::Type>,
+  #[attribute(conflicts = [iterable, array_like])]
+  record: Option<syn::Type>,
+  cast: Option<syn::Ident>,
+  reflect: bool,
+  has: bool,
+  delete: bool,
+  getter_prefix: Option<syn::LitStr>,
+  setter_prefix: Option<syn::LitStr>,
 }
 
 #[derive(Debug, Attribute)]
@@ -835,6 +4192,92 @@ struct FieldAttributes {
   final_: bool,
   structural: bool,
   js_name: Option<syn::Lit>,
+  event_handler: bool,
+  #[attribute(conflicts = [event_handler])]
+  reflect: bool,
+  #[attribute(conflicts = [js_name, event_handler, path])]
+  symbol: Option<syn::Lit>,
+  #[attribute(conflicts = [js_name, event_handler, symbol])]
+  path: Option<syn::Lit>,
+  #[attribute(conflicts = [path])]
+  has: bool,
+  #[attribute(conflicts = [path])]
+  delete: bool,
+  #[attribute(conflicts = [event_handler, reflect, symbol, path])]
+  default: FieldDefault,
+  #[attribute(conflicts = [event_handler])]
+  get_ty: Option<syn::Type>,
+  #[attribute(conflicts = [event_handler])]
+  set_ty: Option<syn::Type>,
+  getter_name: Option<syn::Ident>,
+  setter_name: Option<syn::Ident>,
+}
+
+/// The `default` of a field, either an expression, or
+/// `Default::default()` when given as a flag.
+#[derive(Debug)]
+struct FieldDefault(Option<syn::Expr>);
+
+impl attribute_derive::ConvertParsed for FieldDefault {
+  type Type = syn::Expr;
+
+  fn convert(expr: syn::Expr) -> syn::Result<Self> {
+    Ok(Self(Some(expr)))
+  }
+
+  fn default_by_default() -> bool {
+    true
+  }
+
+  fn default() -> Self {
+    Self(None)
+  }
+
+  fn as_flag() -> Option<syn::Expr> {
+    Some(parse_quote! { ::core::default::Default::default() })
+  }
+}
+
+/// Whether an argument is `optional`, either given as a flag, or as
+/// the name its overloads are suffixed with instead of its ident, e.g.,
+/// `optional = "options"`.
+#[derive(Clone, Debug, Default)]
+struct OptionalArg {
+  enabled: bool,
+  name: Option<syn::Ident>,
+}
+
+impl attribute_derive::ConvertParsed for OptionalArg {
+  type Type = syn::Lit;
+
+  fn convert(lit: syn::Lit) -> syn::Result<Self> {
+    match lit {
+      syn::Lit::Bool(lit) => Ok(Self {
+        enabled: lit.value,
+        name: None,
+      }),
+      syn::Lit::Str(lit) => Ok(Self {
+        enabled: true,
+        name: Some(lit.parse()?),
+      }),
+      lit => Err(syn::Error::new_spanned(
+        lit,
+        "expected a bool, or the name of the overloads as a string",
+      )),
+    }
+  }
+
+  fn default_by_default() -> bool {
+    true
+  }
+
+  fn default() -> Self {
+    <Self as Default>::default()
+  }
+
+  fn as_flag() -> Option<syn::Lit> {
+    Some(parse_quote! { true })
+  }
 }
 
 #[derive(Debug, Attribute)]
@@ -864,6 +4307,23 @@ struct MethodAttributes {
   indexing_deleter: bool,
   js_name: Option<syn::Lit>,
   variadic: bool,
+  overloads: bool,
+  #[attribute(conflicts = [js_name])]
+  overload_of: Option<syn::Lit>,
+  abortable: bool,
+}
+
+#[derive(Clone, Debug, Attribute)]
+#[attribute(ident = opts)]
+struct ArgAttributes {
+  optional: OptionalArg,
+  variadic: bool,
+  /// How long the `Closure` of an `impl Fn*` argument is kept alive,
+  /// one of `leak`, `once` or `guard`, see [`ClosurePolicy`].
+  closure: Option<syn::Ident>,
+  signal: bool,
+  #[attribute(conflicts = [signal])]
+  signal_field: Option<syn::Lit>,
 }
 
 // #[derive(Attribute)]
@@ -924,16 +4384,646 @@ fn apply_getter_rules(global: GetterKind, local: GetterKind) -> GetterKind {
   }
 }
 
-fn is_self_ty_from_return_ty(return_ty: &syn::ReturnType) -> bool {
-  if let syn::ReturnType::Type(_, ty) = return_ty {
-    is_self_ty(ty)
+fn is_self_ty(ty: &syn::Type) -> bool {
+  let self_ty: syn::Type = parse_quote!(Self);
+
+  self_ty == *ty
+}
+
+/// Replaces every occurrence of `Self` within `ty`, no matter how deeply
+/// nested, with `with`.
+fn replace_self_ty(ty: &mut syn::Type, with: &syn::Type) {
+  struct ReplaceSelfTy<'a>(&'a syn::Type);
+
+  impl syn::visit_mut::VisitMut for ReplaceSelfTy<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+      if is_self_ty(ty) {
+        *ty = self.0.clone();
+      } else {
+        syn::visit_mut::visit_type_mut(self, ty);
+      }
+    }
+  }
+
+  syn::visit_mut::VisitMut::visit_type_mut(&mut ReplaceSelfTy(with), ty);
+}
+
+fn is_ident_ty(ty: &syn::Type, path: &syn::Path) -> bool {
+  matches!(
+    ty,
+    syn::Type::Path(syn::TypePath { qself: None, path: ty_path })
+      if ty_path == path
+  )
+}
+
+fn contains_ident(tokens: TokenStream, ident: &syn::Ident) -> bool {
+  tokens.into_iter().any(|tt| match tt {
+    proc_macro2::TokenTree::Ident(tt_ident) => tt_ident == *ident,
+    proc_macro2::TokenTree::Group(group) => {
+      contains_ident(group.stream(), ident)
+    }
+    _ => false,
+  })
+}
+
+/// The lifetimes used anywhere within `inputs` and `output`.
+fn used_lifetimes(
+  inputs: &syn::punctuated::Punctuated<syn::FnArg, syn::Token![,]>,
+  output: &syn::ReturnType,
+) -> Vec<syn::Lifetime> {
+  struct UsedLifetimes(Vec<syn::Lifetime>);
+
+  impl syn::visit_mut::VisitMut for UsedLifetimes {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+      self.0.push(lifetime.clone());
+    }
+  }
+
+  let mut used = UsedLifetimes(vec![]);
+
+  for arg in inputs.clone().iter_mut() {
+    syn::visit_mut::VisitMut::visit_fn_arg_mut(&mut used, arg);
+  }
+
+  syn::visit_mut::VisitMut::visit_return_type_mut(
+    &mut used,
+    &mut output.clone(),
+  );
+
+  used.0
+}
+
+/// Names the variant of a method taking the given optional arguments,
+/// e.g., `add_event_listener_with_options`.
+fn overload_ident(ident: &syn::Ident, optional: &[MethodArg]) -> syn::Ident {
+  if optional.is_empty() {
+    return ident.clone();
+  }
+
+  let optional = optional
+    .iter()
+    .map(|arg| arg.suffix.to_string())
+    .collect::<Vec<_>>()
+    .join("_and_");
+
+  quote::format_ident!("{ident}_with_{optional}")
+}
+
+/// The ident of a pattern binding the whole argument, if it is one.
+fn pat_ident(pat: &syn::Pat) -> Option<&syn::Ident> {
+  match pat {
+    syn::Pat::Ident(syn::PatIdent {
+      by_ref: None,
+      ident,
+      subpat: None,
+      ..
+    }) => Some(ident),
+    _ => None,
+  }
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+  if let syn::Type::Path(syn::TypePath {
+    path: syn::Path { segments, .. },
+    ..
+  }) = ty
+  {
+    segments.last().is_some_and(|segment| segment.ident == "Option")
   } else {
     false
   }
 }
 
-fn is_self_ty(ty: &syn::Type) -> bool {
-  let self_ty: syn::Type = parse_quote!(Self);
+fn is_unit(return_ty: &syn::ReturnType) -> bool {
+  match return_ty {
+    syn::ReturnType::Default => true,
+    syn::ReturnType::Type(_, ty) => is_unit_ty(ty),
+  }
+}
 
-  self_ty == *ty
+/// A typed JS function, `JsFn<(A, B), R>`, bound as an `extern` type
+/// extending `js_sys::Function`, which is generated for each field or
+/// method return using it, e.g., `JsTypeOnMessageFn`.
+///
+/// It is called with `call(a, b)`, whose result is converted to `R`
+/// with `TryFromJsValue`, and created from either a `js_sys::Function`
+/// or any `impl FnMut(A, B) -> R`, which is leaked.
+struct JsFn {
+  args: Vec<syn::Type>,
+  ret: syn::Type,
+}
+
+impl JsFn {
+  fn from_ty(ty: &syn::Type) -> Option<Self> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+      return None;
+    };
+
+    let segment = path.segments.last()?;
+
+    if segment.ident != "JsFn" {
+      return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(
+      syn::AngleBracketedGenericArguments { args, .. },
+    ) = &segment.arguments
+    else {
+      abort!(segment, "`JsFn` must have exactly 2 type arguments");
+    };
+
+    let types = args
+      .iter()
+      .map(|arg| {
+        if let syn::GenericArgument::Type(ty) = arg {
+          ty.clone()
+        } else {
+          abort!(arg, "only types within `JsFn` are supported")
+        }
+      })
+      .collect::<Vec<_>>();
+
+    let [fn_args, ret] = <[_; 2]>::try_from(types).unwrap_or_else(|_| {
+      abort!(args, "`JsFn` must have exactly 2 type arguments")
+    });
+
+    let args = match fn_args {
+      syn::Type::Tuple(syn::TypeTuple { elems, .. }) => {
+        elems.into_iter().collect()
+      }
+      ty => vec![ty],
+    };
+
+    Some(Self { args, ret })
+  }
+
+  /// The type of Rust closures which can be set as this function.
+  fn callback_ty(&self) -> syn::Type {
+    let Self { args, ret } = self;
+
+    let ret = (!is_unit_ty(ret)).then(|| quote! { -> #ret });
+
+    parse_quote! { impl FnMut(#(#args),*) #ret }
+  }
+
+  /// The `dyn` type of the `Closure` created from a callback.
+  fn dyn_callback_ty(&self) -> TokenStream {
+    let syn::Type::ImplTrait(syn::TypeImplTrait { bounds, .. }) =
+      self.callback_ty()
+    else {
+      unreachable!()
+    };
+
+    quote! { dyn #bounds }
+  }
+
+  /// Like [`Self::from_ty`], also matching `Option<JsFn<A, R>>`, in
+  /// which case the `bool` is `true`.
+  fn from_optional_ty(ty: &syn::Type) -> Option<(Self, bool)> {
+    match marker_ty_arg(ty, "Option") {
+      Some(ty) => Self::from_ty(&ty).map(|js_fn| (js_fn, true)),
+      None => Self::from_ty(ty).map(|js_fn| (js_fn, false)),
+    }
+  }
+
+  /// The `extern` type named `ident` wrapping the function, which can
+  /// be called with typed arguments and created from a Rust closure.
+  fn wrapper(
+    &self,
+    vis: &syn::Visibility,
+    ident: &syn::Ident,
+  ) -> TokenStream {
+    let Self { args, ret } = self;
+
+    let params = (0..args.len())
+      .map(|i| quote::format_ident!("arg{i}"))
+      .collect::<Vec<_>>();
+
+    let map_ret = if is_unit_ty(ret) {
+      quote! { .map(|_| ()) }
+    } else {
+      quote! {
+        .and_then(
+          <#ret as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+        )
+      }
+    };
+
+    let callback_ty = self.callback_ty();
+
+    let dyn_ty = self.dyn_callback_ty();
+
+    let syn::Type::ImplTrait(syn::TypeImplTrait { bounds, .. }) = &callback_ty
+    else {
+      unreachable!()
+    };
+
+    let doc_call = "Calls the function, failing if it throws or returns \
+                    another type.";
+
+    let doc_from_closure = "Leaks the closure, so JS can call it for as \
+                            long as the program runs.";
+
+    quote! {
+      #[::wasm_bindgen::prelude::wasm_bindgen]
+      extern "C" {
+        #[doc = "A typed JS function, called with [`Self::call`]."]
+        #[wasm_bindgen(extends = ::js_sys::Function)]
+        #[wasm_bindgen(extends = ::js_sys::Object)]
+        #[wasm_bindgen(is_type_of = ::wasm_bindgen::JsValue::is_function)]
+        #[derive(Clone, Debug)]
+        #vis type #ident;
+      }
+
+      impl #ident {
+        #[doc = #doc_call]
+        #vis fn call(
+          &self,
+          #(#params: #args),*
+        ) -> ::core::result::Result<#ret, ::wasm_bindgen::JsValue> {
+          ::js_sys::Function::apply(
+            self,
+            &::wasm_bindgen::JsValue::UNDEFINED,
+            &[#(::wasm_bindgen::JsValue::from(#params)),*]
+              .into_iter()
+              .collect::<::js_sys::Array>(),
+          )
+          #map_ret
+        }
+      }
+
+      impl ::core::convert::From<::js_sys::Function> for #ident {
+        fn from(function: ::js_sys::Function) -> Self {
+          ::wasm_bindgen::JsCast::unchecked_into(function)
+        }
+      }
+
+      impl<F: #bounds + 'static> ::core::convert::From<F> for #ident {
+        #[doc = #doc_from_closure]
+        fn from(f: F) -> Self {
+          ::wasm_bindgen::JsCast::unchecked_into(
+            ::wasm_bindgen::closure::Closure::<#dyn_ty>::new(f)
+              .into_js_value(),
+          )
+        }
+      }
+    }
+  }
+}
+
+/// A typed JS promise, `Promise<T>`, bound as an `extern` type
+/// extending `js_sys::Promise`, which is generated for each field or
+/// method return using it, e.g., `JsTypeReadyPromise`.
+///
+/// It can be awaited for `Result<T, JsValue>`, whose value is converted
+/// to `T` with `TryFromJsValue`, and converts to and from the
+/// `js_sys::Promise`, so it can be stored, raced or passed back to JS.
+struct JsPromise {
+  ty: syn::Type,
+}
+
+impl JsPromise {
+  fn from_ty(ty: &syn::Type) -> Option<Self> {
+    marker_ty_arg(ty, "Promise").map(|ty| Self { ty })
+  }
+
+  /// The `extern` type named `ident` wrapping the promise, which can be
+  /// awaited for the typed result.
+  fn wrapper(
+    &self,
+    vis: &syn::Visibility,
+    ident: &syn::Ident,
+  ) -> TokenStream {
+    let Self { ty } = self;
+
+    let map_ty = if is_unit_ty(ty) {
+      quote! { .map(|_| ()) }
+    } else {
+      quote! {
+        .and_then(
+          <#ty as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value,
+        )
+      }
+    };
+
+    let doc = format!(
+      "A typed JS promise, which can be awaited for `Result<{}, JsValue>`.",
+      ty.to_token_stream()
+    );
+
+    let is_type_of = quote! {
+      ::wasm_bindgen::JsValue::is_instance_of::<::js_sys::Promise>
+    };
+
+    quote! {
+      #[::wasm_bindgen::prelude::wasm_bindgen]
+      extern "C" {
+        #[doc = #doc]
+        #[wasm_bindgen(extends = ::js_sys::Promise)]
+        #[wasm_bindgen(extends = ::js_sys::Object)]
+        #[wasm_bindgen(is_type_of = #is_type_of)]
+        #[derive(Clone, Debug)]
+        #vis type #ident;
+      }
+
+      impl ::core::future::IntoFuture for #ident {
+        type Output = ::core::result::Result<#ty, ::wasm_bindgen::JsValue>;
+
+        type IntoFuture = ::core::pin::Pin<
+          ::std::boxed::Box<dyn ::core::future::Future<Output = Self::Output>>,
+        >;
+
+        fn into_future(self) -> Self::IntoFuture {
+          ::std::boxed::Box::pin(async move {
+            ::wasm_bindgen_futures::JsFuture::from(
+              ::js_sys::Promise::from(self),
+            )
+            .await
+            #map_ty
+          })
+        }
+      }
+
+      impl ::core::convert::From<::js_sys::Promise> for #ident {
+        fn from(promise: ::js_sys::Promise) -> Self {
+          ::wasm_bindgen::JsCast::unchecked_into(promise)
+        }
+      }
+    }
+  }
+}
+
+/// A typed JS async iterable, `AsyncIter<T>`, bound as `JsValue`.
+///
+/// It is read as a `Stream` of `Result<T, JsValue>`, which calls
+/// `[Symbol.asyncIterator]()` on the first poll and `next()` for each
+/// item, converted to `T` with `TryFromJsValue`, ending after the first
+/// error or item of another type.
+struct JsAsyncIter {
+  ty: syn::Type,
+}
+
+impl JsAsyncIter {
+  fn from_ty(ty: &syn::Type) -> Option<Self> {
+    marker_ty_arg(ty, "AsyncIter").map(|ty| Self { ty })
+  }
+
+  /// The type of the `Stream` reading the iterable.
+  fn stream_ty(&self) -> syn::Type {
+    let Self { ty } = self;
+
+    parse_quote! {
+      impl ::futures::Stream<
+        Item = ::core::result::Result<#ty, ::wasm_bindgen::JsValue>,
+      > + ::core::marker::Unpin
+    }
+  }
+
+  /// Wraps the iterable produced by `iterable` in a `Stream` reading
+  /// it.
+  fn stream(&self, iterable: TokenStream) -> TokenStream {
+    let Self { ty } = self;
+
+    let map_ty = if is_unit_ty(ty) {
+      quote! {
+        |_| ::core::result::Result::<_, ::wasm_bindgen::JsValue>::Ok(())
+      }
+    } else {
+      quote! {
+        <#ty as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value
+      }
+    };
+
+    let iterable_ident =
+      syn::Ident::new("iterable", proc_macro2::Span::mixed_site());
+
+    quote! {
+      {
+        let #iterable_ident: ::wasm_bindgen::JsValue = #iterable;
+
+        ::std::boxed::Box::pin(::futures::stream::try_unfold(
+          (
+            #iterable_ident,
+            ::core::option::Option::None::<::js_sys::AsyncIterator>,
+          ),
+          |(iterable, iterator)| async move {
+            let iterator = match iterator {
+              ::core::option::Option::Some(iterator) => iterator,
+              ::core::option::Option::None => {
+                let method = ::js_sys::Reflect::get(
+                  &iterable,
+                  &::js_sys::Symbol::async_iterator(),
+                )?;
+
+                ::wasm_bindgen::JsCast::unchecked_into(
+                  ::wasm_bindgen::JsCast::unchecked_ref::<::js_sys::Function>(
+                    &method,
+                  )
+                  .call0(&iterable)?,
+                )
+              }
+            };
+
+            let next: ::js_sys::IteratorNext =
+              ::wasm_bindgen::JsCast::unchecked_into(
+                ::wasm_bindgen_futures::JsFuture::from(iterator.next()?)
+                  .await?,
+              );
+
+            if next.done() {
+              return ::core::result::Result::Ok(::core::option::Option::None);
+            }
+
+            // Items of another type fail the stream, like JS errors
+            let item = (#map_ty)(next.value())?;
+
+            ::core::result::Result::<_, ::wasm_bindgen::JsValue>::Ok(
+              ::core::option::Option::Some((
+                item,
+                (iterable, ::core::option::Option::Some(iterator)),
+              )),
+            )
+          },
+        ))
+      }
+    }
+  }
+}
+
+/// Which of `null` and `undefined` a `JsOptional` value can be.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OptionalKind {
+  /// `Nullable<T>`, where `None` is written as `null`.
+  Nullable,
+  /// `Undefinable<T>`, where `None` is written as `undefined`.
+  Undefinable,
+  /// `Maybe<T>`, read as `Option<Option<T>>`, where `None` is
+  /// `undefined` and `Some(None)` is `null`.
+  Maybe,
+}
+
+/// A value which can be missing in a way that matters to JS, bound as
+/// `JsValue`.
+///
+/// `Nullable<T>` and `Undefinable<T>` are read as `Option<T>` from
+/// either `null` or `undefined`, and only differ in how `None` is
+/// written, while `Maybe<T>` keeps the two apart.
+struct JsOptional {
+  kind: OptionalKind,
+  ty: syn::Type,
+}
+
+impl JsOptional {
+  fn from_ty(ty: &syn::Type) -> Option<Self> {
+    [
+      ("Nullable", OptionalKind::Nullable),
+      ("Undefinable", OptionalKind::Undefinable),
+      ("Maybe", OptionalKind::Maybe),
+    ]
+    .into_iter()
+    .find_map(|(marker, kind)| {
+      marker_ty_arg(ty, marker).map(|ty| Self { kind, ty })
+    })
+  }
+
+  /// The Rust type the value is read and written as.
+  fn outer_ty(&self) -> syn::Type {
+    let Self { kind, ty } = self;
+
+    match kind {
+      OptionalKind::Nullable | OptionalKind::Undefinable => {
+        parse_quote! { ::core::option::Option<#ty> }
+      }
+      OptionalKind::Maybe => parse_quote! {
+        ::core::option::Option<::core::option::Option<#ty>>
+      },
+    }
+  }
+
+  /// Converts the `JsValue` produced by `value` to the outer type,
+  /// throwing `error` if it isn't a `T`.
+  fn read(&self, value: TokenStream, error: &str) -> TokenStream {
+    let Self { kind, ty } = self;
+
+    let value_ident = syn::Ident::new("value", proc_macro2::Span::mixed_site());
+
+    let cast = quote! {
+      ::wasm_bindgen::UnwrapThrowExt::expect_throw(
+        <#ty as ::wasm_bindgen::convert::TryFromJsValue>::try_from_js_value(
+          #value_ident,
+        ),
+        #error,
+      )
+    };
+
+    let from_value = match kind {
+      OptionalKind::Nullable | OptionalKind::Undefinable => quote! {
+        if #value_ident.is_null() || #value_ident.is_undefined() {
+          ::core::option::Option::None
+        } else {
+          ::core::option::Option::Some(#cast)
+        }
+      },
+      OptionalKind::Maybe => quote! {
+        if #value_ident.is_undefined() {
+          ::core::option::Option::None
+        } else if #value_ident.is_null() {
+          ::core::option::Option::Some(::core::option::Option::None)
+        } else {
+          ::core::option::Option::Some(::core::option::Option::Some(#cast))
+        }
+      },
+    };
+
+    quote! {
+      let #value_ident: ::wasm_bindgen::JsValue = #value;
+
+      #from_value
+    }
+  }
+
+  /// Converts the outer type produced by `value` to a `JsValue`.
+  fn write(&self, value: TokenStream) -> TokenStream {
+    match self.kind {
+      OptionalKind::Nullable => quote! {
+        #value.map_or(
+          ::wasm_bindgen::JsValue::NULL,
+          ::wasm_bindgen::JsValue::from,
+        )
+      },
+      OptionalKind::Undefinable => quote! {
+        #value.map_or(
+          ::wasm_bindgen::JsValue::UNDEFINED,
+          ::wasm_bindgen::JsValue::from,
+        )
+      },
+      OptionalKind::Maybe => quote! {
+        match #value {
+          ::core::option::Option::None => ::wasm_bindgen::JsValue::UNDEFINED,
+          ::core::option::Option::Some(::core::option::Option::None) => {
+            ::wasm_bindgen::JsValue::NULL
+          }
+          ::core::option::Option::Some(::core::option::Option::Some(value)) => {
+            ::wasm_bindgen::JsValue::from(value)
+          }
+        }
+      },
+    }
+  }
+}
+
+/// The single type argument of the `marker` type, e.g., `T` in
+/// `Promise<T>`, or `None` if `ty` isn't `marker` with type arguments.
+fn marker_ty_arg(ty: &syn::Type, marker: &str) -> Option<syn::Type> {
+  let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+    return None;
+  };
+
+  let segment = path.segments.last()?;
+
+  // Markers without type arguments are left as-is, e.g., `Promise` is
+  // `js_sys::Promise` itself
+  if segment.ident != marker || segment.arguments.is_empty() {
+    return None;
+  }
+
+  let syn::PathArguments::AngleBracketed(
+    syn::AngleBracketedGenericArguments { args, .. },
+  ) = &segment.arguments
+  else {
+    abort!(segment, "`{}` must have exactly 1 type argument", marker);
+  };
+
+  if args.len() != 1 {
+    abort!(args, "`{}` must have exactly 1 type argument", marker);
+  }
+
+  let syn::GenericArgument::Type(ty) = &args[0] else {
+    abort!(args[0], "only types within `{}` are supported", marker);
+  };
+
+  Some(ty.clone())
+}
+
+/// Whether `ty` is one of the types `wasm-bindgen` converts JS values
+/// to, rather than casts them to, e.g., `String`, numbers, `Option<T>`
+/// or `Vec<T>`.
+fn is_converted_ty(ty: &syn::Type) -> bool {
+  const CONVERTED: &[&str] = &[
+    "String", "bool", "char", "i8", "i16", "i32", "i64", "i128", "isize",
+    "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64", "Option",
+    "Vec",
+  ];
+
+  match ty {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => path
+      .segments
+      .last()
+      .is_some_and(|segment| CONVERTED.iter().any(|ty| segment.ident == ty)),
+    ty => is_unit_ty(ty),
+  }
+}
+
+fn is_unit_ty(ty: &syn::Type) -> bool {
+  matches!(ty, syn::Type::Tuple(tuple) if tuple.elems.is_empty())
 }
@@ -0,0 +1,358 @@
+//! Expansions compiled against the runtime crates they depend on, as
+//! the macro tests only compare tokens.
+
+#![allow(dead_code)]
+// Methods declare their bindings in `extern` blocks within their body
+#![allow(non_local_definitions)]
+// Bodiless methods only forward their arguments, so must drop `mut`
+#![deny(unused_mut)]
+
+use core::future::Future;
+use futures::{StreamExt, TryStreamExt};
+use js_sys::Object;
+use std::collections::{HashMap, VecDeque};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_struct::wasm_bindgen_struct;
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct Event {}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct AbortSignal {}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct RequestInit {}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct Point {
+  pub x: u32,
+  pub y: u32,
+}
+
+#[wasm_bindgen_struct]
+#[opts(
+  extends = Object,
+  event("message", Event),
+  event("DOMContentLoaded", Event),
+  event("app:ready", Event, app_ready),
+)]
+pub struct Target {
+  pub name: String,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct Worker {
+  pub format: JsFn<(u32, String), String>,
+  pub on_done: Option<JsFn<(Event,), ()>>,
+  pub ready: Promise<String>,
+  pub entries: AsyncIter<u32>,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object, iterable = String)]
+pub struct Names {}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object, iterable = u32, cast = checked)]
+pub struct Counts {}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object, iterable = Target, cast = checked)]
+pub struct Targets {}
+
+#[wasm_bindgen_struct]
+#[opts(array_like = u8)]
+pub struct Bytes {}
+
+#[wasm_bindgen_struct]
+#[opts(array_like = String, cast = checked)]
+pub struct Lines {}
+
+#[wasm_bindgen_struct]
+pub struct Button {
+  #[opts(event_handler)]
+  pub onclick: Event,
+}
+
+#[wasm_bindgen_struct]
+#[opts(record = String)]
+pub struct Headers {}
+
+#[wasm_bindgen_struct]
+#[opts(record = u32, cast = checked)]
+pub struct Scores {}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct Element {
+  #[opts(reflect, js_name = "data-id")]
+  pub data_id: String,
+  #[opts(symbol = "Symbol.toStringTag", getter)]
+  pub tag: String,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object, reflect)]
+pub struct Options {
+  pub mode: u32,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct Config {
+  #[opts(path = "server?.port")]
+  pub port: f64,
+  #[opts(path = "server.host")]
+  pub host: String,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct Props {
+  #[opts(has, delete)]
+  pub my_prop: String,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object, has, delete)]
+pub struct Flags {
+  pub enabled: bool,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct Dialog {
+  pub title: Nullable<String>,
+  pub width: Undefinable<u32>,
+  pub timeout: Maybe<f64>,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct Timer {
+  #[opts(default = 5000.0)]
+  pub timeout: f64,
+  #[opts(default)]
+  pub name: String,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object)]
+pub struct Input {
+  #[opts(set_ty = &str)]
+  pub name: String,
+  #[opts(get_ty = String, set_ty = &JsValue)]
+  pub value: JsValue,
+}
+
+#[wasm_bindgen_struct]
+#[opts(extends = Object, getter_prefix = "get_", setter_prefix = "with_")]
+pub struct Widget {
+  pub name: String,
+  #[opts(getter_name = is_visible)]
+  pub visible: bool,
+  #[opts(default = 0, setter_name = resize)]
+  pub size: u32,
+}
+
+#[wasm_bindgen_struct]
+impl Target {
+  fn for_each(&self, f: impl FnMut(JsValue));
+
+  fn set_timeout(f: impl FnOnce() + 'static, delay: u32) -> i32;
+
+  fn then(&self, #[opts(closure = guard)] f: impl Fn(u32) -> u32) -> u32;
+
+  fn respond_with(
+    &self,
+    f: impl FnMut(Event) -> impl Future<Output = Result<JsValue, JsValue>>,
+  );
+
+  async fn load(&self, url: &str) -> Result<JsValue, JsValue>;
+
+  fn factory(&self, f: JsFn<(u32, u32), u32>) -> JsFn<(u32,), String>;
+
+  fn format_with(&self, format: JsFn<(u32, String), String>) -> String;
+
+  #[opts(variadic)]
+  fn log(values: &[String]);
+
+  fn push(&self, #[opts(variadic)] values: &VecDeque<u32>);
+
+  fn append<'a, T: Into<JsValue>>(
+    &self,
+    value: T,
+    key: &'a str,
+    node: &impl AsRef<Object>,
+  );
+
+  fn count(&self) -> Promise<u32>;
+
+  fn settle(&self, after: Promise<()>) -> Promise<Self>;
+
+  fn keys(&self) -> AsyncIter<String>;
+
+  fn ticks(&self) -> AsyncIter<()>;
+
+  #[opts(abortable)]
+  async fn read(&self, #[opts(signal)] signal: &AbortSignal) -> JsValue;
+
+  #[opts(abortable)]
+  async fn fetch(
+    &self,
+    input: &str,
+    #[opts(signal_field = "signal")] init: &RequestInit,
+  ) -> Result<JsValue, JsValue>;
+
+  fn move_to(&self, _: u32, mut label: String, Point { x, y }: Point);
+
+  fn scale(Point { x, y }: Point, mut factor: u32) -> u32 {
+    factor *= 2;
+
+    Self::scale_js(Point { x: y, y: x }, factor)
+  }
+}
+
+fn use_js_fns(worker: &Worker, target: &Target) -> Result<(), JsValue> {
+  worker.set_format(|a: u32, b: String| format!("{a}{b}"));
+  worker.set_format(worker.format());
+  worker.set_on_done(Some(WorkerOnDoneFn::from(|_: Event| ())));
+  worker.set_on_done(None);
+
+  let _: Option<WorkerOnDoneFn> = worker.on_done();
+  let _: String = worker.format().call(1, "a".into())?;
+  let _: String = target.factory(|a, b| a + b).call(1)?;
+  let _: String = target.format_with(|a: u32, b: String| format!("{b}{a}"));
+  let _: String = target.format_with(js_sys::Function::from(worker.format()));
+
+  Ok(())
+}
+
+fn use_events(target: &Target) -> impl futures::Stream<Item = Event> {
+  let _listener: TargetListener = target.on_message(|_| {});
+  let _listener = target.on_dom_content_loaded(|_| {});
+
+  target.events_app_ready()
+}
+
+async fn await_promises(
+  worker: &Worker,
+  target: &Target,
+) -> Result<(), JsValue> {
+  let _: String = worker.ready().await?;
+  let _: u32 = target.count().await?;
+  let _: Target = target.settle(worker.ready()).await?;
+
+  let promise = js_sys::Promise::resolve(&JsValue::from("ready"));
+
+  worker.set_ready(&promise);
+  worker.set_ready(WorkerReadyPromise::from(promise));
+
+  Ok(())
+}
+
+async fn read_async_iters(
+  worker: &Worker,
+  target: &Target,
+) -> Result<(), JsValue> {
+  let _: Vec<u32> = worker.entries().try_collect().await?;
+  let _: Option<Result<String, JsValue>> = target.keys().next().await;
+  let _: Vec<()> = target.ticks().try_collect().await?;
+
+  Ok(())
+}
+
+async fn abort_on_drop(
+  target: &Target,
+  init: &RequestInit,
+) -> Result<JsValue, JsValue> {
+  let _: JsValue = target.read().await;
+
+  target.fetch("/", init).await
+}
+
+async fn wait_for_messages(target: &Target, count: usize) {
+  let mut messages = target.events_message();
+
+  for _ in 0..count {
+    if messages.next().await.is_none() {
+      break;
+    }
+  }
+}
+
+fn iterate(names: &Names, counts: &Counts, targets: &Targets) {
+  let _: Vec<String> = names.iter().collect();
+  let _: Result<Vec<u32>, JsValue> = counts.iter().collect();
+  let _: Result<Vec<Target>, JsValue> = targets.into_iter().collect();
+}
+
+fn index(bytes: &Bytes, lines: &Lines) {
+  let _: Vec<u8> = bytes.iter().collect();
+  let _: Option<Result<String, JsValue>> = lines.get(0);
+  let _: Vec<Result<String, JsValue>> = lines.to_vec();
+}
+
+fn handle_clicks(button: &Button) -> ButtonEventHandler {
+  button.set_onclick(|_| {})
+}
+
+fn read_records(headers: &Headers, scores: &Scores) {
+  let _: Option<String> = headers.get("accept");
+  let _: Vec<(String, Result<u32, JsValue>)> = scores.entries();
+  let _: HashMap<String, String> = headers.into();
+  let _: Result<HashMap<String, u32>, JsValue> = scores.try_into();
+  let _ = Headers::from(HashMap::from([("a".to_string(), "b".to_string())]));
+  let _ = Scores::from(HashMap::from([("a".to_string(), 1)]));
+}
+
+fn use_reflect(element: &Element, options: &Options) -> Result<(), JsValue> {
+  element.set_data_id(element.data_id()?)?;
+  options.set_mode(options.mode()? + 1)?;
+
+  let _: String = element.tag()?;
+
+  Ok(())
+}
+
+fn use_paths(config: &Config) -> Result<(), JsValue> {
+  let _: Option<f64> = config.port()?;
+
+  config.set_port(8080.0)?;
+  config.set_host(config.host()?)
+}
+
+fn check_presence(props: &Props, flags: &Flags) -> bool {
+  (props.has_my_prop() && props.delete_my_prop())
+    || (flags.has_enabled() && flags.delete_enabled())
+}
+
+fn use_optionals(dialog: &Dialog) {
+  dialog.set_title(dialog.title().or(Some("untitled".to_owned())));
+  dialog.set_width(dialog.width().map(|width| width * 2));
+  dialog.set_timeout(match dialog.timeout() {
+    None => Some(None),
+    Some(timeout) => Some(timeout.map(|timeout| timeout + 1.0)),
+  });
+}
+
+fn use_defaults(timer: &Timer) {
+  timer.set_timeout(timer.timeout() / 2.0);
+  timer.set_name(timer.name() + " (halved)");
+}
+
+fn use_field_types(input: &Input) {
+  input.set_name(&input.name());
+  input.set_value(&JsValue::from_str(&input.value()));
+}
+
+fn use_method_names(widget: &Widget) {
+  widget.with_name(widget.get_name());
+  widget.with_visible(!widget.is_visible());
+  widget.resize(widget.get_size() + 1);
+}